@@ -0,0 +1,64 @@
+use node_bindgen::{
+    core::{buffer::ArrayBuffer, val::JsEnv, NjError, TryIntoJs},
+    sys::napi_value,
+};
+use serde::Serialize;
+use session::events::ComputationError;
+use std::convert::TryFrom;
+
+/// Encoding of data, which is passed from rust to JS. JSON is delivered as
+/// string; binary encodings are delivered as ArrayBuffer, which owns the
+/// encoded bytes without copying them into JS heap.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum Encoding {
+    #[default]
+    Json,
+    MessagePack,
+}
+
+impl Encoding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::MessagePack => "msgpack",
+        }
+    }
+
+    pub fn to_js<T: Serialize>(&self, value: &T, js_env: &JsEnv) -> Result<napi_value, NjError> {
+        match self {
+            Self::Json => match serde_json::to_string(value) {
+                Ok(s) => js_env.create_string_utf8(&s),
+                Err(e) => Err(NjError::Other(format!("Could not convert into json: {e}"))),
+            },
+            Self::MessagePack => {
+                let mut bytes = Vec::new();
+                // Maps and human-readable values (uuid as string, etc.) keep
+                // decoded data in the same shape as JSON does
+                value
+                    .serialize(
+                        &mut rmp_serde::Serializer::new(&mut bytes)
+                            .with_struct_map()
+                            .with_human_readable(),
+                    )
+                    .map_err(|e| {
+                        NjError::Other(format!("Could not convert into MessagePack: {e}"))
+                    })?;
+                ArrayBuffer::new(bytes).try_to_js(js_env)
+            }
+        }
+    }
+}
+
+impl TryFrom<&str> for Encoding {
+    type Error = ComputationError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "json" => Ok(Self::Json),
+            "msgpack" => Ok(Self::MessagePack),
+            _ => Err(ComputationError::InvalidArgs(format!(
+                "Unsupported encoding: {value}"
+            ))),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::js::session::events::ComputationErrorWrapper;
+use crate::js::{encoding::Encoding, session::events::ComputationErrorWrapper};
 use log::{debug, error};
 use node_bindgen::{
     core::{val::JsEnv, NjError, TryIntoJs},
@@ -6,9 +6,14 @@ use node_bindgen::{
     sys::napi_value,
 };
 use serde::Serialize;
+use serde_json::Value;
 use session::{
     events::ComputationError,
-    unbound::{api::UnboundSessionAPI, commands::CommandOutcome, UnboundSession},
+    unbound::{
+        api::UnboundSessionAPI,
+        commands::{CommandOutcome, OnDuplicate},
+        UnboundSession,
+    },
 };
 use std::{convert::TryFrom, thread};
 use tokio::runtime::Runtime;
@@ -17,19 +22,15 @@ use tokio_util::sync::CancellationToken;
 struct UnboundJobs {
     api: Option<UnboundSessionAPI>,
     finished: CancellationToken,
+    encoding: Encoding,
 }
 
-pub(crate) struct CommandOutcomeWrapper<T: Serialize>(pub CommandOutcome<T>);
+pub(crate) struct CommandOutcomeWrapper<T: Serialize>(pub CommandOutcome<T>, pub Encoding);
 
 impl<T: Serialize> TryIntoJs for CommandOutcomeWrapper<T> {
-    /// serialize into json object
+    /// serialize with negotiated encoding
     fn try_to_js(self, js_env: &JsEnv) -> Result<napi_value, NjError> {
-        match serde_json::to_string(&self.0) {
-            Ok(s) => js_env.create_string_utf8(&s),
-            Err(e) => Err(NjError::Other(format!(
-                "Could not convert Callback event to json: {e}"
-            ))),
-        }
+        self.1.to_js(&self.0, js_env)
     }
 }
 
@@ -41,6 +42,24 @@ fn id_from_i64(id: i64) -> Result<u64, ComputationErrorWrapper> {
     })
 }
 
+fn args_from_str(args: &str) -> Result<Value, ComputationErrorWrapper> {
+    serde_json::from_str(args).map_err(|e| {
+        ComputationErrorWrapper(ComputationError::InvalidArgs(format!(
+            "Arguments of job should be JSON: {e}"
+        )))
+    })
+}
+
+/// If `replace` is true, a running or queued job with the same id is cancelled
+/// instead of rejecting the new one
+fn on_duplicate(replace: bool) -> OnDuplicate {
+    if replace {
+        OnDuplicate::Replace
+    } else {
+        OnDuplicate::Reject
+    }
+}
+
 #[node_bindgen]
 impl UnboundJobs {
     // Self methods
@@ -49,9 +68,17 @@ impl UnboundJobs {
         Self {
             api: None,
             finished: CancellationToken::new(),
+            encoding: Encoding::default(),
         }
     }
 
+    /// Sets encoding of results of jobs. Returns accepted encoding
+    #[node_bindgen]
+    fn set_encoding(&mut self, encoding: String) -> Result<String, ComputationErrorWrapper> {
+        self.encoding = Encoding::try_from(encoding.as_str())?;
+        Ok(self.encoding.as_str().to_string())
+    }
+
     #[node_bindgen(mt)]
     async fn init(&mut self) -> Result<(), ComputationErrorWrapper> {
         let rt = Runtime::new().map_err(|e| {
@@ -98,19 +125,74 @@ impl UnboundJobs {
             .map_err(ComputationErrorWrapper)
     }
 
+    /// Returns stats of results cache as JSON string
+    #[node_bindgen]
+    async fn get_cache_stats(&self) -> Result<String, ComputationErrorWrapper> {
+        let stats = self
+            .api
+            .as_ref()
+            .ok_or(ComputationError::SessionUnavailable)?
+            .cache_stats()
+            .await?;
+        Ok(serde_json::to_string(&stats)?)
+    }
+
+    #[node_bindgen]
+    fn clear_cache(&self) -> Result<(), ComputationErrorWrapper> {
+        self.api
+            .as_ref()
+            .ok_or(ComputationError::SessionUnavailable)?
+            .clear_cache()
+            .map_err(ComputationErrorWrapper)
+    }
+
+    /// Runs job with given alias (see `UnboundCommand::ALIAS`); `args` is JSON
+    /// object with arguments of job
     #[node_bindgen]
-    async fn job_cancel_test(
+    async fn run(
+        &self,
+        id: i64,
+        alias: String,
+        args: String,
+        replace: bool,
+    ) -> Result<CommandOutcomeWrapper<Value>, ComputationErrorWrapper> {
+        self.api
+            .as_ref()
+            .ok_or(ComputationError::SessionUnavailable)?
+            .run_by_alias(
+                id_from_i64(id)?,
+                &alias,
+                args_from_str(&args)?,
+                on_duplicate(replace),
+            )
+            .await
+            .map_err(ComputationErrorWrapper)
+            .map(|outcome| CommandOutcomeWrapper(outcome, self.encoding))
+    }
+
+    /// Same as run, but delivers intermediate results of job into callback
+    #[node_bindgen(mt)]
+    async fn run_with_partials<F: Fn(CommandOutcomeWrapper<Value>) + Send + 'static>(
         &self,
         id: i64,
-        custom_arg_a: i64,
-        custom_arg_b: i64,
-    ) -> Result<CommandOutcomeWrapper<i64>, ComputationErrorWrapper> {
+        alias: String,
+        args: String,
+        replace: bool,
+        callback: F,
+    ) -> Result<CommandOutcomeWrapper<Value>, ComputationErrorWrapper> {
+        let encoding = self.encoding;
         self.api
             .as_ref()
             .ok_or(ComputationError::SessionUnavailable)?
-            .cancel_test(id_from_i64(id)?, custom_arg_a, custom_arg_b)
+            .run_by_alias_with_partials(
+                id_from_i64(id)?,
+                &alias,
+                args_from_str(&args)?,
+                on_duplicate(replace),
+                move |partial| callback(CommandOutcomeWrapper(partial, encoding)),
+            )
             .await
             .map_err(ComputationErrorWrapper)
-            .map(CommandOutcomeWrapper)
+            .map(|outcome| CommandOutcomeWrapper(outcome, encoding))
     }
 }
@@ -1,3 +1,4 @@
+use crate::js::encoding::Encoding;
 use node_bindgen::{
     core::{val::JsEnv, NjError, TryIntoJs},
     sys::napi_value,
@@ -5,23 +6,18 @@ use node_bindgen::{
 use session::events::{CallbackEvent, ComputationError, LifecycleTransition};
 
 #[derive(Debug)]
-pub(crate) struct CallbackEventWrapper(pub CallbackEvent);
+pub(crate) struct CallbackEventWrapper(pub CallbackEvent, pub Encoding);
 
 impl TryIntoJs for CallbackEventWrapper {
-    /// serialize into json object
+    /// serialize with negotiated encoding
     fn try_to_js(self, js_env: &JsEnv) -> Result<napi_value, NjError> {
-        match serde_json::to_string(&self.0) {
-            Ok(s) => js_env.create_string_utf8(&s),
-            Err(e) => Err(NjError::Other(format!(
-                "Could not convert Callback event to json: {e}"
-            ))),
-        }
+        self.1.to_js(&self.0, js_env)
     }
 }
 
 impl From<CallbackEvent> for CallbackEventWrapper {
     fn from(e: CallbackEvent) -> CallbackEventWrapper {
-        CallbackEventWrapper(e)
+        CallbackEventWrapper(e, Encoding::default())
     }
 }
 impl From<serde_json::Error> for ComputationErrorWrapper {
@@ -46,16 +42,11 @@ impl From<ComputationError> for ComputationErrorWrapper {
 }
 
 #[derive(Debug)]
-pub(crate) struct LifecycleTransitionWrapper(pub LifecycleTransition);
+pub(crate) struct LifecycleTransitionWrapper(pub LifecycleTransition, pub Encoding);
 
 impl TryIntoJs for LifecycleTransitionWrapper {
-    /// serialize into json object
+    /// serialize with negotiated encoding
     fn try_to_js(self, js_env: &JsEnv) -> Result<napi_value, NjError> {
-        match serde_json::to_string(&self.0) {
-            Ok(s) => js_env.create_string_utf8(&s),
-            Err(e) => Err(NjError::Other(format!(
-                "Could not convert Callback event to json: {e}"
-            ))),
-        }
+        self.1.to_js(&self.0, js_env)
     }
 }
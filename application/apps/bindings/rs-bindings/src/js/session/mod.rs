@@ -1,7 +1,7 @@
 pub mod events;
 pub mod progress_tracker;
 
-use crate::js::session::events::ComputationErrorWrapper;
+use crate::js::{encoding::Encoding, session::events::ComputationErrorWrapper};
 use events::CallbackEventWrapper;
 use log::{debug, error};
 use node_bindgen::derive::node_bindgen;
@@ -10,30 +10,63 @@ use session::{
     operations,
     session::Session,
 };
-use std::thread;
+use std::{convert::TryFrom, thread};
 use tokio::{runtime::Runtime, sync::oneshot};
 use uuid::Uuid;
 
+/// Parses uuid of operation if it's given by caller; otherwise uuid would be
+/// allocated by session
+fn parse_operation_id(operation_id: Option<String>) -> Result<Option<Uuid>, ComputationError> {
+    operation_id
+        .map(|id| operations::uuid_from_str(&id))
+        .transpose()
+}
+
 struct RustSession {
     session: Option<Session>,
-    uuid: Uuid,
+    uuid: Option<Uuid>,
+    encoding: Encoding,
 }
 
 #[node_bindgen]
 impl RustSession {
+    /// Creates an empty instance; session should be assigned to an UUID
+    /// with `create` before init
     #[node_bindgen(constructor)]
-    pub fn new(id: String) -> Self {
-        let uuid = match operations::uuid_from_str(&id) {
-            Ok(uuid) => uuid,
-            Err(err) => {
-                // TODO: Should be replaced with error
-                panic!("Fail to convert UUID = {}; error:{}", id, err);
-            }
-        };
+    pub fn new() -> Self {
         Self {
             session: None,
-            uuid,
+            uuid: None,
+            encoding: Encoding::default(),
+        }
+    }
+
+    /// Validates and assigns UUID of session
+    #[node_bindgen]
+    fn create(&mut self, id: String) -> Result<(), ComputationErrorWrapper> {
+        if self.uuid.is_some() {
+            return Err(ComputationErrorWrapper(ComputationError::MultipleInitCall));
+        }
+        let uuid = operations::uuid_from_str(&id).map_err(|err| {
+            ComputationErrorWrapper(ComputationError::InvalidArgs(format!(
+                "Fail to convert UUID = {id}; error: {err}"
+            )))
+        })?;
+        self.uuid = Some(uuid);
+        Ok(())
+    }
+
+    /// Sets encoding of callback events. Should be called before init.
+    /// Returns accepted encoding
+    #[node_bindgen]
+    fn set_encoding(&mut self, encoding: String) -> Result<String, ComputationErrorWrapper> {
+        if self.session.is_some() {
+            return Err(ComputationErrorWrapper(ComputationError::Protocol(
+                String::from("Encoding cannot be changed after init"),
+            )));
         }
+        self.encoding = Encoding::try_from(encoding.as_str())?;
+        Ok(self.encoding.as_str().to_string())
     }
 
     #[node_bindgen(mt)]
@@ -45,7 +78,12 @@ impl RustSession {
             ComputationError::Process(format!("Could not start tokio runtime: {e}"))
         })?;
         let (tx_session, rx_session) = oneshot::channel();
-        let uuid = self.uuid;
+        let uuid = self.uuid.ok_or_else(|| {
+            ComputationErrorWrapper(ComputationError::InvalidArgs(String::from(
+                "UUID of session isn't assigned; create should be called before init",
+            )))
+        })?;
+        let encoding = self.encoding;
         thread::spawn(move || {
             rt.block_on(async {
                 match Session::new(uuid).await {
@@ -56,10 +94,13 @@ impl RustSession {
                         }
                         debug!("task is started");
                         while let Some(event) = rx_callback_events.recv().await {
-                            callback(event.into())
+                            callback(CallbackEventWrapper(event, encoding))
                         }
                         debug!("sending SessionDestroyed event");
-                        callback(CallbackEvent::SessionDestroyed.into());
+                        callback(CallbackEventWrapper(
+                            CallbackEvent::SessionDestroyed,
+                            encoding,
+                        ));
                         debug!("task is finished");
                     }
                     Err(e) => {
@@ -93,15 +134,16 @@ impl RustSession {
     #[node_bindgen]
     fn abort(
         &self,
-        operation_id: String,
+        operation_id: Option<String>,
         target_id: String,
-    ) -> Result<(), ComputationErrorWrapper> {
+    ) -> Result<String, ComputationErrorWrapper> {
         if let Some(ref session) = self.session {
             session
                 .abort(
-                    operations::uuid_from_str(&operation_id)?,
+                    parse_operation_id(operation_id)?,
                     operations::uuid_from_str(&target_id)?,
                 )
+                .map(|uuid| uuid.to_string())
                 .map_err(ComputationErrorWrapper)
         } else {
             Err(ComputationErrorWrapper(
@@ -111,10 +153,10 @@ impl RustSession {
     }
 
     #[node_bindgen]
-    async fn stop(&self, operation_id: String) -> Result<(), ComputationErrorWrapper> {
+    async fn stop(&self, operation_id: Option<String>) -> Result<(), ComputationErrorWrapper> {
         if let Some(ref session) = self.session {
             session
-                .stop(operations::uuid_from_str(&operation_id)?)
+                .stop(parse_operation_id(operation_id)?)
                 .await
                 .map_err(ComputationErrorWrapper)?;
             Ok(())
@@ -156,10 +198,15 @@ impl RustSession {
     }
 
     #[node_bindgen]
-    async fn sleep(&self, operation_id: String, ms: i64) -> Result<(), ComputationErrorWrapper> {
+    async fn sleep(
+        &self,
+        operation_id: Option<String>,
+        ms: i64,
+    ) -> Result<String, ComputationErrorWrapper> {
         if let Some(ref session) = self.session {
             session
-                .sleep(operations::uuid_from_str(&operation_id)?, ms as u64)
+                .sleep(parse_operation_id(operation_id)?, ms as u64)
+                .map(|uuid| uuid.to_string())
                 .map_err(ComputationErrorWrapper)
         } else {
             Err(ComputationErrorWrapper(
@@ -171,21 +218,22 @@ impl RustSession {
     #[node_bindgen]
     async fn external_call_lib(
         &self,
-        operation_id: String,
+        operation_id: Option<String>,
         path: String,
         a: i64,
         b: i64,
         lines: Vec<String>,
-    ) -> Result<(), ComputationErrorWrapper> {
+    ) -> Result<String, ComputationErrorWrapper> {
         if let Some(ref session) = self.session {
             session
                 .external_call_lib(
-                    operations::uuid_from_str(&operation_id)?,
+                    parse_operation_id(operation_id)?,
                     path,
                     a as u64,
                     b as u64,
                     lines,
                 )
+                .map(|uuid| uuid.to_string())
                 .map_err(ComputationErrorWrapper)
         } else {
             Err(ComputationErrorWrapper(
@@ -196,29 +244,41 @@ impl RustSession {
 
     #[node_bindgen]
     async fn trigger_state_error(&self) -> Result<(), ComputationErrorWrapper> {
+        #[cfg(feature = "fault-injection")]
         if let Some(ref session) = self.session {
-            session
+            return session
                 .trigger_state_error()
                 .await
-                .map_err(ComputationErrorWrapper)
-        } else {
-            Err(ComputationErrorWrapper(
-                ComputationError::SessionUnavailable,
-            ))
+                .map_err(ComputationErrorWrapper);
         }
+        #[cfg(not(feature = "fault-injection"))]
+        if self.session.is_some() {
+            return Err(ComputationErrorWrapper(ComputationError::Protocol(
+                String::from("Bindings are built without fault-injection feature"),
+            )));
+        }
+        Err(ComputationErrorWrapper(
+            ComputationError::SessionUnavailable,
+        ))
     }
 
     #[node_bindgen]
     async fn trigger_tracker_error(&self) -> Result<(), ComputationErrorWrapper> {
+        #[cfg(feature = "fault-injection")]
         if let Some(ref session) = self.session {
-            session
+            return session
                 .trigger_tracker_error()
                 .await
-                .map_err(ComputationErrorWrapper)
-        } else {
-            Err(ComputationErrorWrapper(
-                ComputationError::SessionUnavailable,
-            ))
+                .map_err(ComputationErrorWrapper);
+        }
+        #[cfg(not(feature = "fault-injection"))]
+        if self.session.is_some() {
+            return Err(ComputationErrorWrapper(ComputationError::Protocol(
+                String::from("Bindings are built without fault-injection feature"),
+            )));
         }
+        Err(ComputationErrorWrapper(
+            ComputationError::SessionUnavailable,
+        ))
     }
 }
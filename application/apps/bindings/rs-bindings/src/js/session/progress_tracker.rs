@@ -1,16 +1,18 @@
 use super::events::{ComputationErrorWrapper, LifecycleTransitionWrapper};
+use crate::js::encoding::Encoding;
 use log::trace;
 use node_bindgen::derive::node_bindgen;
 use session::{
     events::ComputationError,
     progress::{run_tracking, ProgressCommand, ProgressTrackerAPI},
 };
-use std::thread;
+use std::{convert::TryFrom, thread};
 use tokio::{runtime::Runtime, sync::mpsc::UnboundedReceiver};
 
 struct RustProgressTracker {
     tracker_api: ProgressTrackerAPI,
     rx_events: Option<UnboundedReceiver<ProgressCommand>>,
+    encoding: Encoding,
 }
 
 #[node_bindgen]
@@ -21,9 +23,23 @@ impl RustProgressTracker {
         Self {
             tracker_api,
             rx_events: Some(rx_events),
+            encoding: Encoding::default(),
         }
     }
 
+    /// Sets encoding of lifecycle events. Should be called before init.
+    /// Returns accepted encoding
+    #[node_bindgen]
+    fn set_encoding(&mut self, encoding: String) -> Result<String, ComputationErrorWrapper> {
+        if self.rx_events.is_none() {
+            return Err(ComputationErrorWrapper(ComputationError::Protocol(
+                "Encoding cannot be changed after init".to_string(),
+            )));
+        }
+        self.encoding = Encoding::try_from(encoding.as_str())?;
+        Ok(self.encoding.as_str().to_string())
+    }
+
     #[node_bindgen(mt)]
     async fn init<F: Fn(LifecycleTransitionWrapper) + Send + 'static>(
         &mut self,
@@ -32,6 +48,7 @@ impl RustProgressTracker {
         let rt = Runtime::new().map_err(|e| {
             ComputationError::Process(format!("Could not start tokio runtime: {e}"))
         })?;
+        let encoding = self.encoding;
         if let Some(rx_events) = self.rx_events.take() {
             let (result_tx, result_rx) = std::sync::mpsc::channel();
             thread::spawn(move || {
@@ -41,7 +58,7 @@ impl RustProgressTracker {
                         Ok(mut rx) => {
                             let _ = result_tx.send(Ok(()));
                             while let Some(progress_report) = rx.recv().await {
-                                callback(LifecycleTransitionWrapper(progress_report))
+                                callback(LifecycleTransitionWrapper(progress_report, encoding))
                             }
                         }
                         Err(e) => {
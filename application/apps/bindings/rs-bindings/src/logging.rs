@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
 use log::{Level, LevelFilter, Metadata, Record};
 use log4rs::{
     append::file::FileAppender,
@@ -45,7 +45,7 @@ fn init_module() {
 }
 
 pub fn init_logging() -> Result<()> {
-    let log_config_path = elrust_log_config();
+    let log_config_path = elrust_log_config()?;
     let logging_correctly_initialized = if log_config_path.exists() {
         // log4rs.yaml exists, try to parse it
         match log4rs::init_file(&log_config_path, Default::default()) {
@@ -66,26 +66,27 @@ pub fn init_logging() -> Result<()> {
     Ok(())
 }
 
-pub fn elrust_home_dir() -> PathBuf {
+pub fn elrust_home_dir() -> Result<PathBuf> {
     let home_dir = dirs::home_dir()
-        .expect("we need to have access to home-dir")
+        .ok_or_else(|| anyhow!("no access to home-dir"))?
         .join(".elrust");
     if !home_dir.exists() {
         fs::create_dir(&home_dir)
-            .unwrap_or_else(|_| panic!("home folder {:?} should be created", home_dir));
+            .with_context(|| format!("home folder {home_dir:?} cannot be created"))?;
     }
-    home_dir
+    Ok(home_dir)
 }
 
-pub fn elrust_log_config() -> PathBuf {
-    elrust_home_dir().join("log4rs.yaml")
+pub fn elrust_log_config() -> Result<PathBuf> {
+    Ok(elrust_home_dir()?.join("log4rs.yaml"))
 }
 
 pub fn initialize_from_fresh_yml() -> Result<()> {
     println!("Initialization of logs is started on rs-bindings layer");
-    let log_config_path = elrust_log_config();
-    let indexer_log_path = elrust_home_dir().join("elrust.rustcore.log");
-    let launcher_log_path = elrust_home_dir().join("elrust.launcher.log");
+    let home_dir = elrust_home_dir()?;
+    let log_config_path = home_dir.join("log4rs.yaml");
+    let indexer_log_path = home_dir.join("elrust.rustcore.log");
+    let launcher_log_path = home_dir.join("elrust.launcher.log");
     let log_config_content = std::include_str!("../log4rs.yaml")
         .replace("$INDEXER_LOG_PATH", &indexer_log_path.to_string_lossy())
         .replace("$LAUNCHER_LOG_PATH", &launcher_log_path.to_string_lossy());
@@ -110,7 +111,7 @@ pub fn remove_entity(entity: &Path) -> Result<()> {
 
 pub fn setup_fallback_logging() -> Result<()> {
     println!("[setup_fallback_logging]: Initialization of logs is started on rs-bindings layer");
-    let log_path = elrust_home_dir().join("elrust.launcher.log");
+    let log_path = elrust_home_dir()?.join("elrust.launcher.log");
     let appender_name = "startup-appender";
     let logfile = FileAppender::builder()
         .encoder(Box::new(PatternEncoder::new("{d} - {l}:: {m}\n")))
@@ -123,9 +124,9 @@ pub fn setup_fallback_logging() -> Result<()> {
                 .appender(appender_name)
                 .build(LevelFilter::Warn),
         )
-        .expect("log4rs config could not be created");
+        .context("log4rs config could not be created")?;
 
-    log4rs::init_config(config).expect("logging could not be initialized");
+    log4rs::init_config(config).context("logging could not be initialized")?;
     println!("[setup_fallback_logging]:Initialization of logs is finished");
     Ok(())
 }
@@ -5,6 +5,7 @@ import { Session } from '../src/api/session';
 import { finish, runner } from './common';
 import { readConfigurationFile } from './config';
 import { error } from 'platform/log/utils';
+import { classify, EErrorCode, EErrorKind } from '../src/provider/provider.errors';
 
 const config = readConfigurationFile().get().tests.errors;
 
@@ -66,4 +67,48 @@ describe('Errors', () => {
                 });
         });
     });
+
+    it(config.regular.list[3], function () {
+        return runner(config.regular, 3, async (logger, done, collector) => {
+            expect(classify(EErrorCode.FileNotFound)).toBe(EErrorKind.FileNotFound);
+            expect(classify(EErrorCode.UnsupportedFileType)).toBe(EErrorKind.UnsupportedFileType);
+            expect(classify(EErrorCode.ComputationFailed)).toBe(EErrorKind.ComputationFailed);
+            expect(classify(EErrorCode.Configuration)).toBe(EErrorKind.Configuration);
+            expect(classify(EErrorCode.Interrupted)).toBe(EErrorKind.Interrupted);
+            expect(classify(EErrorCode.OperationSearch)).toBe(EErrorKind.OperationSearch);
+            expect(classify(EErrorCode.NotYetImplemented)).toBe(EErrorKind.NotYetImplemented);
+            expect(classify(EErrorCode.ChannelError)).toBe(EErrorKind.ChannelError);
+            expect(classify(EErrorCode.Io)).toBe(EErrorKind.Io);
+            expect(classify(EErrorCode.Grabber)).toBe(EErrorKind.Grabber);
+            expect(classify(EErrorCode.InvalidArgs)).toBe(EErrorKind.InvalidArgs);
+            expect(classify(EErrorCode.InvalidData)).toBe(EErrorKind.InvalidData);
+            // Codes should be in sync with NativeErrorKind::code on rust side
+            expect(EErrorCode.FileNotFound).toBe(1001);
+            expect(EErrorCode.InvalidData).toBe(1012);
+            finish(undefined, done);
+        });
+    });
+
+    it(config.regular.list[4], function () {
+        return runner(config.regular, 4, async (logger, done, collector) => {
+            expect(classify(EErrorCode.DestinationPath)).toBe(EErrorKind.Configuration);
+            expect(classify(EErrorCode.Communication)).toBe(EErrorKind.ChannelError);
+            expect(classify(EErrorCode.OperationNotSupported)).toBe(EErrorKind.NotYetImplemented);
+            expect(classify(EErrorCode.IoOperation)).toBe(EErrorKind.Io);
+            expect(classify(EErrorCode.InvalidComputationData)).toBe(EErrorKind.InvalidData);
+            expect(classify(EErrorCode.InvalidComputationArgs)).toBe(EErrorKind.InvalidArgs);
+            expect(classify(EErrorCode.Process)).toBe(EErrorKind.ComputationFailed);
+            expect(classify(EErrorCode.Protocol)).toBe(EErrorKind.ComputationFailed);
+            expect(classify(EErrorCode.MultipleInitCall)).toBe(EErrorKind.Configuration);
+            expect(classify(EErrorCode.SessionUnavailable)).toBe(EErrorKind.ComputationFailed);
+            expect(classify(EErrorCode.Sde)).toBe(EErrorKind.Io);
+            // Codes should be in sync with ComputationError::code on rust side
+            expect(EErrorCode.DestinationPath).toBe(2001);
+            expect(EErrorCode.Sde).toBe(2012);
+            // Unknown codes (including 2011, which is never sent) are not classified
+            expect(classify(2011)).toBe(EErrorKind.something);
+            expect(classify(0)).toBe(EErrorKind.something);
+            finish(undefined, done);
+        });
+    });
 });
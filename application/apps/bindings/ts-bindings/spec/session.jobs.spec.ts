@@ -26,13 +26,13 @@ describe('Jobs', function () {
             tracker.provider.getEvents().Stopped.subscribe((uuid) => {
                 operations.set(uuid, false);
             });
-            jobs.cancelTest(50, 50)
+            jobs.run('cancelTest', { custom_arg_a: 50, custom_arg_b: 50 })
                 .then((a) => {
                     // Job is resolved, but not cancelled
                     expect(a).toBe(100);
                     // Try to cancel job
                     const job = jobs
-                        .cancelTest(50, 50)
+                        .run('cancelTest', { custom_arg_a: 50, custom_arg_b: 50 })
                         .then((_res) => {
                             expect(operations.size).toBe(2);
                             expect(
@@ -65,8 +65,12 @@ describe('Jobs', function () {
             const jobs = collector(await Jobs.create()) as Jobs;
             // Run 2 jobs with same sequence. One of jobs should be failed, because of sequence
             Promise.allSettled([
-                jobs.cancelTest(50, 50, 0).asPromise(),
-                jobs.cancelTest(50, 50, 0).asPromise(),
+                jobs
+                    .run('cancelTest', { custom_arg_a: 50, custom_arg_b: 50 }, { sequence: 0 })
+                    .asPromise(),
+                jobs
+                    .run('cancelTest', { custom_arg_a: 50, custom_arg_b: 50 }, { sequence: 0 })
+                    .asPromise(),
             ])
                 .then((res) => {
                     if (
@@ -89,4 +93,33 @@ describe('Jobs', function () {
                 });
         });
     });
+
+    it(config.regular.list[3], function () {
+        return runner(config.regular, 3, async (logger, done, collector) => {
+            const jobs = collector(await Jobs.create()) as Jobs;
+            // Second job replaces the first one: first is cancelled, second is done
+            const first = jobs.run(
+                'cancelTest',
+                { custom_arg_a: 50, custom_arg_b: 50 },
+                { sequence: 0 },
+            );
+            let cancelled = false;
+            first.canceled(() => {
+                cancelled = true;
+            });
+            jobs.run(
+                'cancelTest',
+                { custom_arg_a: 25, custom_arg_b: 25 },
+                { sequence: 0, replace: true },
+            )
+                .then((res: number) => {
+                    expect(res).toBe(50);
+                    expect(cancelled).toBe(true);
+                    finish(jobs, done);
+                })
+                .catch((err: Error) => {
+                    finish(jobs, done, err);
+                });
+        });
+    });
 });
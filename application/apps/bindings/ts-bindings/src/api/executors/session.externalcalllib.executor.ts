@@ -44,7 +44,7 @@ export const executor: TExecutor<IExternalCallLibResults, IExecuteExternalCallLi
             reject: (err: Error) => void,
         ) {
             try {
-                const result: [number, string | undefined] = JSON.parse(data);
+                const result: [number, string | undefined] = data;
                 if (result instanceof Array && result.length === 2) {
                     return resolve({ sum: result[0], found: result[1] });
                 }
@@ -29,8 +29,10 @@ export const executor: TExecutor<ISleepResults, IExecuteSleepOptions> = (
         },
         function (data: any, resolve: (res: ISleepResults) => void, reject: (err: Error) => void) {
             try {
-                const result: ISleepResults = JSON.parse(data);
-                resolve(result);
+                if (typeof data !== 'object' || data === null) {
+                    return reject(new Error(`Sleep results should be an object`));
+                }
+                resolve(data as ISleepResults);
             } catch (e) {
                 return reject(
                     new Error(
@@ -1,32 +1,104 @@
 import { CancelablePromise } from 'platform/env/promise';
-import { Base } from '../native/native.jobs';
+import { Base, RunOptions } from '../native/native.jobs';
+import { EEncoding } from '../provider/provider.general';
+
+export enum EntityType {
+    File = 'File',
+    Directory = 'Directory',
+    SymbolicLink = 'SymbolicLink',
+    Other = 'Other',
+}
+
+export interface EntityDetails {
+    filename: string;
+    full_filename: string;
+    path: string;
+    basename: string;
+    ext: string;
+    size: number;
+    modified: number | null;
+    readonly: boolean;
+}
+
+export interface Entity {
+    name: string;
+    fullname: string;
+    kind: EntityType;
+    details: EntityDetails | null;
+}
+
+export interface FolderContent {
+    list: Entity[];
+    max_len_reached: boolean;
+}
+
+export interface FileFormat {
+    encoding: 'Ascii' | 'Utf8' | 'Utf8Bom' | 'Utf16Le' | 'Utf16Be' | 'Unknown';
+    file_type: 'Text' | 'Binary';
+}
+
+// Same as SearchFilter of backend
+export interface SearchFilter {
+    value: string;
+    is_regex: boolean;
+    ignore_case: boolean;
+    is_word: boolean;
+}
+
+// Arguments and results of jobs by alias. Aliases and arguments are the same as
+// jobs of backend have (see UnboundCommand::ALIAS), so a job, which is added
+// into backend's registry, becomes available by adding it here.
+export interface JobsMap {
+    cancelTest: { args: { custom_arg_a: number; custom_arg_b: number }; output: number };
+    getFolderContent: {
+        args: {
+            paths: string[];
+            depth: number;
+            max_len: number;
+            include_files: boolean;
+            include_folders: boolean;
+        };
+        output: FolderContent;
+    };
+    checksum: { args: { filename: string }; output: string };
+    getFileFormat: { args: { filename: string }; output: FileFormat };
+    getRegexError: { args: { filter: SearchFilter }; output: string | null };
+    getSerialPortsList: { args: Record<string, never>; output: string[] };
+}
+
+export type JobAlias = keyof JobsMap;
 
 export class Jobs extends Base {
-    public static async create(): Promise<Jobs> {
-        const instance = new Jobs();
+    public static async create(encoding?: EEncoding): Promise<Jobs> {
+        const instance = new Jobs(encoding);
         await instance.init();
         return instance;
     }
 
-    // This method is used for testing
-    public cancelTest(num_a: number, num_b: number, seq?: number): CancelablePromise<number> {
-        const sequence = seq === undefined ? this.sequence() : seq;
-        const job: CancelablePromise<number> = this.execute(
-            // We should define validation callback. As argument it takes result of job,
-            // which should be checked for type. In case it type is correct, callback
-            // should return true
-            (res: number): number | Error => {
-                return typeof res === 'number'
-                    ? res
-                    : new Error(`jobCancelTest should return number type`);
-            },
-            // As second argument of executor we should provide native function of job.
-            this.native.jobCancelTest(sequence, num_a, num_b),
-            // Sequence of job
+    // Runs job with given alias. With options.onPartial intermediate results
+    // of job are delivered into callback while job is running
+    public run<A extends JobAlias>(
+        alias: A,
+        args: JobsMap[A]['args'],
+        options?: RunOptions<JobsMap[A]['output']>,
+    ): CancelablePromise<JobsMap[A]['output']> {
+        const sequence = this.sequence(options);
+        const replace = options?.replace === true;
+        const serialized = JSON.stringify(args);
+        const onPartial = options?.onPartial;
+        return this.execute<JobsMap[A]['output'], JobsMap[A]['output']>(
+            undefined,
+            onPartial === undefined
+                ? this.native.run(sequence, alias, serialized, replace)
+                : this.native.runWithPartials(
+                      sequence,
+                      alias,
+                      serialized,
+                      replace,
+                      this.partials(alias, onPartial),
+                  ),
             sequence,
-            // Alias of job for logs
-            'cancelTest',
+            alias,
         );
-        return job;
     }
 }
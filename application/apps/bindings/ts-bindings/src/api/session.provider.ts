@@ -1,11 +1,28 @@
 import { Subject } from 'platform/env/subscription';
 import { Computation } from '../provider/provider';
-import { EErrorKind, EErrorSeverity } from '../provider/provider.errors';
+import {
+    EErrorKind,
+    EErrorSeverity,
+    ESeverity,
+    ErrorContext,
+    ISourceLocation,
+} from '../provider/provider.errors';
 
 export interface IError {
     severity: EErrorSeverity;
     kind: EErrorKind;
+    code: number;
     message?: string;
+    context: ErrorContext[];
+    causes: string[];
+    location?: ISourceLocation;
+    origin?: unknown;
+}
+
+export interface INotification {
+    severity: ESeverity;
+    content: string;
+    line?: number;
 }
 
 export interface IErrorEvent {
@@ -21,6 +38,7 @@ export interface IOperationDoneEvent {
 export interface ISessionEvents {
     SessionError: Subject<IError>;
     OperationError: Subject<IErrorEvent>;
+    SessionNotification: Subject<INotification>;
     SessionDestroyed: Subject<void>;
     OperationStarted: Subject<string>;
     OperationProcessing: Subject<string>;
@@ -32,6 +50,7 @@ export interface ISessionEventsConvertors {}
 interface ISessionEventsSignatures {
     SessionError: 'SessionError';
     OperationError: 'OperationError';
+    SessionNotification: 'SessionNotification';
     SessionDestroyed: 'SessionDestroyed';
     OperationStarted: 'OperationStarted';
     OperationProcessing: 'OperationProcessing';
@@ -41,6 +60,7 @@ interface ISessionEventsSignatures {
 const SessionEventsSignatures: ISessionEventsSignatures = {
     SessionError: 'SessionError',
     OperationError: 'OperationError',
+    SessionNotification: 'SessionNotification',
     SessionDestroyed: 'SessionDestroyed',
     OperationStarted: 'OperationStarted',
     OperationProcessing: 'OperationProcessing',
@@ -54,6 +74,7 @@ interface ISessionEventsInterfaces {
         uuid: 'string';
         error: { self: 'object'; severity: 'string'; message: 'string'; kind: 'string' };
     };
+    SessionNotification: { self: 'object'; severity: 'string'; content: 'string' };
     SessionDestroyed: { self: null };
     OperationStarted: { self: 'string' };
     OperationProcessing: { self: 'string' };
@@ -67,6 +88,7 @@ const SessionEventsInterfaces: ISessionEventsInterfaces = {
         uuid: 'string',
         error: { self: 'object', severity: 'string', message: 'string', kind: 'string' },
     },
+    SessionNotification: { self: 'object', severity: 'string', content: 'string' },
     SessionDestroyed: { self: null },
     OperationStarted: { self: 'string' },
     OperationProcessing: { self: 'string' },
@@ -81,6 +103,7 @@ export class EventProvider extends Computation<
     private readonly _events: ISessionEvents = {
         SessionError: new Subject<IError>(),
         OperationError: new Subject<IErrorEvent>(),
+        SessionNotification: new Subject<INotification>(),
         SessionDestroyed: new Subject<void>(),
         OperationStarted: new Subject<string>(),
         OperationProcessing: new Subject<string>(),
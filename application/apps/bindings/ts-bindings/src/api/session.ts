@@ -5,6 +5,7 @@ import { Subscription } from 'platform/env/subscription';
 import { RustSession, RustSessionConstructor } from '../native/native.session';
 import { EventProvider, ISessionEvents, IError } from '../api/session.provider';
 import { IOrderStat } from '../provider/provider';
+import { EEncoding } from '../provider/provider.general';
 import { Executors } from './executors/session.executors';
 import { ISleepResults } from './executors/session.sleep.executor';
 import { IExternalCallLibResults } from './executors/session.externalcalllib.executor';
@@ -33,7 +34,7 @@ export class Session {
         native: [],
     };
 
-    public static create(): Promise<Session> {
+    public static create(encoding?: EEncoding): Promise<Session> {
         return new Promise((resolve, reject) => {
             new Session((session: Error | Session) => {
                 if (session instanceof Error) {
@@ -41,11 +42,11 @@ export class Session {
                 } else {
                     resolve(session);
                 }
-            });
+            }, encoding);
         });
     }
 
-    constructor(cb: (err: Error | Session) => void) {
+    constructor(cb: (err: Error | Session) => void, encoding?: EEncoding) {
         this._logger = scope.getLogger(`Session: ${this._uuid}`);
         this._provider = new EventProvider(this._uuid);
         this._session = new RustSessionConstructor(
@@ -78,6 +79,7 @@ export class Session {
                     cb(this);
                 }
             },
+            encoding,
         );
     }
 
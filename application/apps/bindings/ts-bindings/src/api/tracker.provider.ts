@@ -14,6 +14,10 @@ export interface Ticks {
     count: number;
     state: string | undefined;
     total: number | undefined;
+    // Ticks per second
+    throughput: number | undefined;
+    // Estimated time remaining in ms
+    eta: number | undefined;
 }
 
 export interface ISessionEvents {
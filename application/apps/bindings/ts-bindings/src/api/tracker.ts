@@ -4,6 +4,8 @@ import { getNativeModule } from '../native/native';
 import { TrackerNative } from '../native/native.tracker';
 import { EventProvider } from './tracker.provider';
 import { unique } from 'platform/env/sequence';
+import { error } from 'platform/log/utils';
+import { EEncoding } from '../provider/provider.general';
 
 enum State {
     destroyed,
@@ -13,8 +15,8 @@ enum State {
 }
 
 export class Tracker {
-    public static async create(): Promise<Tracker> {
-        const instance = new Tracker();
+    public static async create(encoding?: EEncoding): Promise<Tracker> {
+        const instance = new Tracker(encoding);
         await instance.init();
         return instance;
     }
@@ -27,10 +29,17 @@ export class Tracker {
 
     private _state: State = State.created;
 
-    constructor() {
+    constructor(encoding: EEncoding = EEncoding.json) {
         this.native = new (getNativeModule().RustProgressTracker)() as TrackerNative;
         this.provider = new EventProvider(this.uuid);
         this.logger.debug(`Rust Tracker native session is created`);
+        // Encoding can be changed only before init
+        try {
+            const accepted = this.native.setEncoding(encoding);
+            this.logger.debug(`Encoding of events: ${accepted}`);
+        } catch (err) {
+            this.logger.error(`Fail to set encoding "${encoding}": ${error(err)}`);
+        }
     }
 
     public async init(): Promise<Tracker> {
@@ -9,6 +9,7 @@ export { CancelablePromise, PromiseExecutor, ICancelablePromise } from 'platform
 export { Session, ISessionEvents } from './api/session';
 export { Jobs } from './api/jobs';
 export { Tracker } from './api/tracker';
+export { EEncoding } from './provider/provider.general';
 
 export { Units, Events, Interfaces };
 
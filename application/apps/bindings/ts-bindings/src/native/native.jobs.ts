@@ -3,15 +3,37 @@ import { scope } from 'platform/env/scope';
 import { CancelablePromise } from 'platform/env/promise';
 import { error } from 'platform/log/utils';
 import { getNativeModule } from '../native/native';
+import { EEncoding, decode } from '../provider/provider.general';
 
 export abstract class JobsNative {
     public abstract abort(sequence: number): Promise<void>;
 
     public abstract init(): Promise<void>;
 
+    // Returns accepted encoding
+    public abstract setEncoding(encoding: string): string;
+
     public abstract destroy(): Promise<void>;
 
-    public abstract jobCancelTest(sequence: number, num_a: number, num_b: number): Promise<string>;
+    public abstract getCacheStats(): Promise<string>;
+
+    public abstract clearCache(): void;
+
+    // Runs job with given alias; args is JSON object with arguments of job
+    public abstract run(
+        sequence: number,
+        alias: string,
+        args: string,
+        replace: boolean,
+    ): Promise<string>;
+
+    public abstract runWithPartials(
+        sequence: number,
+        alias: string,
+        args: string,
+        replace: boolean,
+        callback: (partial: string | ArrayBuffer) => void,
+    ): Promise<string>;
 }
 
 interface Job {
@@ -39,10 +61,32 @@ export class Queue {
     }
 }
 
+export interface CacheStats {
+    hits: number;
+    misses: number;
+    entries: number;
+}
+
 export type JobResult<T> = { Finished: T } | 'Cancelled';
 
+export type JobPartialResult<T> = { Partial: T };
+
+export type PartialCallback<T> = (partial: T) => void;
+
 export type ConvertCallback<Input, Output> = (input: Input) => Output | Error;
 
+// Defines sequence of job. With replace === true a running or queued job with
+// the same sequence is cancelled instead of rejecting the new job; useful to
+// debounce repeated requests
+export interface JobOptions {
+    sequence?: number;
+    replace?: boolean;
+}
+
+export interface RunOptions<T> extends JobOptions {
+    onPartial?: PartialCallback<T>;
+}
+
 enum State {
     destroyed,
     destroying,
@@ -57,9 +101,15 @@ export class Base {
 
     private _state: State = State.created;
 
-    constructor() {
+    constructor(encoding: EEncoding = EEncoding.json) {
         this.native = new (getNativeModule().UnboundJobs)() as JobsNative;
         this.logger.debug(`Rust Jobs native session is created`);
+        try {
+            const accepted = this.native.setEncoding(encoding);
+            this.logger.debug(`Encoding of results: ${accepted}`);
+        } catch (err) {
+            this.logger.error(`Fail to set encoding "${encoding}": ${error(err)}`);
+        }
     }
 
     public async init(): Promise<Base> {
@@ -127,8 +177,34 @@ export class Base {
         });
     }
 
-    protected sequence(): number {
-        return this.queue.sequence();
+    protected sequence(options?: JobOptions): number {
+        return options?.sequence === undefined ? this.queue.sequence() : options.sequence;
+    }
+
+    public async getCacheStats(): Promise<CacheStats> {
+        const stats = await this.native.getCacheStats();
+        return JSON.parse(stats) as CacheStats;
+    }
+
+    public clearCache(): void {
+        this.native.clearCache();
+    }
+
+    // Creates native callback, which parses intermediate results of job
+    protected partials<T>(
+        alias: string,
+        callback: PartialCallback<T>,
+    ): (partial: string | ArrayBuffer) => void {
+        return (partial: string | ArrayBuffer) => {
+            try {
+                const result: JobPartialResult<T> = decode(partial);
+                callback(result.Partial);
+            } catch (e) {
+                this.logger.error(
+                    `Fail to parse intermediate results of "${alias}" (${partial}): ${error(e)}`,
+                );
+            }
+        };
     }
 
     protected execute<Input, Output>(
@@ -151,9 +227,9 @@ export class Base {
                     this.logger.error(`Fail to cancel ${error(err)}`);
                 });
             });
-            task.then((nativeOutput: string) => {
+            task.then((nativeOutput: string | ArrayBuffer) => {
                 try {
-                    const result: JobResult<Input> = JSON.parse(nativeOutput);
+                    const result: JobResult<Input> = decode(nativeOutput);
                     if (result === 'Cancelled' || self.isCanceling()) {
                         if (result !== 'Cancelled' && self.isCanceling()) {
                             this.logger.warn('Job result dropped due canceling');
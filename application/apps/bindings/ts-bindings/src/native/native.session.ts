@@ -1,6 +1,6 @@
 /* eslint-disable @typescript-eslint/no-unused-vars */
 import { RustSessionRequiered } from '../native/native.session.required';
-import { TEventEmitter } from '../provider/provider.general';
+import { TEventEmitter, EEncoding } from '../provider/provider.general';
 import { Computation } from '../provider/provider';
 import { getNativeModule } from '../native/native';
 import { Type, Source, NativeError } from '../interfaces/errors';
@@ -12,6 +12,7 @@ export type RustSessionConstructorImpl<T> = new (
     uuid: string,
     provider: Computation<any, any, any>,
     cb: (err: Error | undefined) => void,
+    encoding?: EEncoding,
 ) => T;
 export type TCanceler = () => void;
 
@@ -53,31 +54,40 @@ export abstract class RustSession extends RustSessionRequiered {
 }
 
 export abstract class RustSessionNative {
-    public abstract stop(operationUuid: string): Promise<void>;
+    // Assigns UUID of session; throws an error if UUID is invalid
+    public abstract create(uuid: string): void;
+
+    // If uuid of operation isn't given, it would be allocated on rust side
+    public abstract stop(operationUuid?: string): Promise<void>;
+
+    // Should be called before init. Returns accepted encoding
+    public abstract setEncoding(encoding: string): string;
 
     public abstract init(callback: TEventEmitter): Promise<void>;
 
     public abstract getUuid(): string;
 
+    // Returns uuid of abort operation
     public abstract abort(
-        selfOperationUuid: string,
+        selfOperationUuid: string | undefined,
         targetOperationUuid: string,
-    ): NativeError | undefined;
+    ): string;
 
     public abstract setDebug(debug: boolean): Promise<void>;
 
     public abstract getOperationsStat(): Promise<string>;
 
+    // Returns uuid of operation
     public abstract externalCallLib(
-        operationUuid: string,
+        operationUuid: string | undefined,
         path: string,
         a: number,
         b: number,
         lines: string[],
-    ): Promise<void>;
+    ): Promise<string>;
 
-    // Used only for testing and debug
-    public abstract sleep(operationUuid: string, duration: number): Promise<void>;
+    // Used only for testing and debug. Returns uuid of operation
+    public abstract sleep(operationUuid: string | undefined, duration: number): Promise<string>;
 
     // Used only for testing and debug
     public abstract triggerStateError(): Promise<void>;
@@ -89,15 +99,21 @@ export abstract class RustSessionNative {
 export function rustSessionFactory(
     uuid: string,
     provider: Computation<any, any, any>,
+    encoding?: EEncoding,
 ): Promise<RustSession> {
     return new Promise((resolve, reject) => {
-        const session = new RustSessionConstructor(uuid, provider, (err: Error | undefined) => {
-            if (err instanceof Error) {
-                reject(err);
-            } else {
-                resolve(session);
-            }
-        });
+        const session = new RustSessionConstructor(
+            uuid,
+            provider,
+            (err: Error | undefined) => {
+                if (err instanceof Error) {
+                    reject(err);
+                } else {
+                    resolve(session);
+                }
+            },
+            encoding,
+        );
     });
 }
 
@@ -112,12 +128,27 @@ export class RustSessionWrapper extends RustSession {
         uuid: string,
         provider: Computation<any, any, any>,
         cb: (err: Error | undefined) => void,
+        encoding: EEncoding = EEncoding.json,
     ) {
         super(uuid, provider);
-        this._native = new (getNativeModule().RustSession)(uuid) as RustSessionNative;
-        this._logger.debug(`Rust native session is created`);
+        this._native = new (getNativeModule().RustSession)() as RustSessionNative;
         this._uuid = uuid;
         this._provider = provider;
+        try {
+            this._native.create(uuid);
+        } catch (err) {
+            const error = new NativeError(NativeError.from(err), Type.InvalidInput, Source.Other);
+            this._logger.error(`Fail to create session: ${error.message}`);
+            cb(error);
+            return;
+        }
+        this._logger.debug(`Rust native session is created`);
+        try {
+            const accepted = this._native.setEncoding(encoding);
+            this._logger.debug(`Encoding of events: ${accepted}`);
+        } catch (err) {
+            this._logger.error(`Fail to set encoding "${encoding}": ${utils.error(err)}`);
+        }
         this._provider.debug().emit.operation('init');
         this._native
             .init(provider.getEmitter())
@@ -168,7 +199,8 @@ export class RustSessionWrapper extends RustSession {
     public abort(selfOperationUuid: string, targetOperationUuid: string): NativeError | undefined {
         try {
             this._provider.debug().emit.operation('abort', selfOperationUuid);
-            return this._native.abort(selfOperationUuid, targetOperationUuid);
+            this._native.abort(selfOperationUuid, targetOperationUuid);
+            return undefined;
         } catch (err) {
             return new NativeError(NativeError.from(err), Type.CancelationError, Source.Abort);
         }
@@ -213,7 +245,7 @@ export class RustSessionWrapper extends RustSession {
             this._provider.debug().emit.operation('externalCallLib', operationUuid);
             this._native
                 .externalCallLib(operationUuid, path, a, b, lines)
-                .then(resolve)
+                .then(() => resolve())
                 .catch((err) => {
                     reject(new NativeError(NativeError.from(err), Type.Other, Source.Sleep));
                 });
@@ -226,7 +258,7 @@ export class RustSessionWrapper extends RustSession {
             this._provider.debug().emit.operation('sleep', operationUuid);
             this._native
                 .sleep(operationUuid, duration)
-                .then(resolve)
+                .then(() => resolve())
                 .catch((err) => {
                     reject(new NativeError(NativeError.from(err), Type.Other, Source.Sleep));
                 });
@@ -1,6 +1,7 @@
 import { TEventEmitter } from '../provider/provider.general';
 
 export abstract class TrackerNative {
+    public abstract setEncoding(encoding: string): string;
     public abstract init(callback: TEventEmitter): Promise<void>;
     public abstract destroy(): Promise<void>;
     public abstract stats(): Promise<string>;
@@ -4,6 +4,112 @@ export enum EErrorSeverity {
     logs = 'logs',
 }
 
+/**
+ * Severity of native errors and notifications as it comes from rust side
+ */
+export enum ESeverity {
+    error = 'ERROR',
+    warning = 'WARNING',
+    info = 'INFO',
+    debug = 'DEBUG',
+}
+
 export enum EErrorKind {
     something = 'something',
-}
\ No newline at end of file
+    FileNotFound = 'FileNotFound',
+    UnsupportedFileType = 'UnsupportedFileType',
+    ComputationFailed = 'ComputationFailed',
+    Configuration = 'Configuration',
+    Interrupted = 'Interrupted',
+    OperationSearch = 'OperationSearch',
+    NotYetImplemented = 'NotYetImplemented',
+    ChannelError = 'ChannelError',
+    Io = 'Io',
+    Grabber = 'Grabber',
+    InvalidArgs = 'InvalidArgs',
+    InvalidData = 'InvalidData',
+}
+
+/**
+ * Stable numeric codes of errors. Should be in sync with
+ * NativeErrorKind::code and ComputationError::code on rust side
+ */
+export enum EErrorCode {
+    FileNotFound = 1001,
+    UnsupportedFileType = 1002,
+    ComputationFailed = 1003,
+    Configuration = 1004,
+    Interrupted = 1005,
+    OperationSearch = 1006,
+    NotYetImplemented = 1007,
+    ChannelError = 1008,
+    Io = 1009,
+    Grabber = 1010,
+    InvalidArgs = 1011,
+    InvalidData = 1012,
+    DestinationPath = 2001,
+    Communication = 2002,
+    OperationNotSupported = 2003,
+    IoOperation = 2004,
+    InvalidComputationData = 2005,
+    InvalidComputationArgs = 2006,
+    Process = 2007,
+    Protocol = 2008,
+    MultipleInitCall = 2009,
+    SessionUnavailable = 2010,
+    Sde = 2012,
+}
+
+export type ErrorContext = { Operation: string } | { Session: string } | { File: string };
+
+export interface ISourceLocation {
+    file: string;
+    line: number;
+    column: number;
+}
+
+/**
+ * Returns kind of error by its code. Errors of computations are mapped
+ * to the nearest kind of native errors.
+ */
+export function classify(code: number): EErrorKind {
+    switch (code) {
+        case EErrorCode.FileNotFound:
+            return EErrorKind.FileNotFound;
+        case EErrorCode.UnsupportedFileType:
+            return EErrorKind.UnsupportedFileType;
+        case EErrorCode.ComputationFailed:
+        case EErrorCode.Process:
+        case EErrorCode.Protocol:
+        case EErrorCode.SessionUnavailable:
+            return EErrorKind.ComputationFailed;
+        case EErrorCode.Configuration:
+        case EErrorCode.DestinationPath:
+        case EErrorCode.MultipleInitCall:
+            return EErrorKind.Configuration;
+        case EErrorCode.Interrupted:
+            return EErrorKind.Interrupted;
+        case EErrorCode.OperationSearch:
+            return EErrorKind.OperationSearch;
+        case EErrorCode.NotYetImplemented:
+        case EErrorCode.OperationNotSupported:
+            return EErrorKind.NotYetImplemented;
+        case EErrorCode.ChannelError:
+        case EErrorCode.Communication:
+            return EErrorKind.ChannelError;
+        case EErrorCode.Io:
+        case EErrorCode.IoOperation:
+        case EErrorCode.Sde:
+            return EErrorKind.Io;
+        case EErrorCode.Grabber:
+            return EErrorKind.Grabber;
+        case EErrorCode.InvalidArgs:
+        case EErrorCode.InvalidComputationArgs:
+            return EErrorKind.InvalidArgs;
+        case EErrorCode.InvalidData:
+        case EErrorCode.InvalidComputationData:
+            return EErrorKind.InvalidData;
+        default:
+            return EErrorKind.something;
+    }
+}
@@ -1,7 +1,28 @@
+import { decode as decodeMsgPack } from '@msgpack/msgpack';
+
 export interface IEventData {
     [key: string]: any;
 }
 
-export type TEventData = string | Required<IEventData>;
+export type TEventData = string | ArrayBuffer | Required<IEventData>;
+
+/**
+ * Encoding of data coming from rust. JSON is delivered as string,
+ * MessagePack as ArrayBuffer
+ */
+export enum EEncoding {
+    json = 'json',
+    msgpack = 'msgpack',
+}
+
+/**
+ * Decodes data coming from rust in any of supported encodings
+ * @param data {string | ArrayBuffer}
+ */
+export function decode<T>(data: string | ArrayBuffer): T {
+    return (
+        data instanceof ArrayBuffer ? decodeMsgPack(new Uint8Array(data)) : JSON.parse(data)
+    ) as T;
+}
 
 export type TEventEmitter = (event: TEventData) => void;
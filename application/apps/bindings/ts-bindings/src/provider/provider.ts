@@ -7,7 +7,7 @@ import { Subject, validateEventDesc } from 'platform/env/subscription';
 import { error } from 'platform/log/utils';
 import { Logger } from 'platform/log';
 import { scope } from 'platform/env/scope';
-import { TEventData, TEventEmitter, IEventData } from '../provider/provider.general';
+import { TEventData, TEventEmitter, IEventData, decode } from '../provider/provider.general';
 
 export interface IOrderStat {
     type: 'E' | 'O';
@@ -230,6 +230,8 @@ export abstract class Computation<TEvents, IEventsSignatures, IEventsInterfaces>
             let message = '';
             if (typeof data === 'string') {
                 message = `(defined as string): ${data}`;
+            } else if (data instanceof ArrayBuffer) {
+                message = `(defined as ArrayBuffer): ${data.byteLength} bytes`;
             } else {
                 message = `(defined as object): keys: ${Object.keys(data).join(
                     ', ',
@@ -246,11 +248,11 @@ export abstract class Computation<TEvents, IEventsSignatures, IEventsInterfaces>
         this.logger.debug(`Event from rust:\n\t${logs.debug}`);
         logs.verb !== undefined && this.logger.verbose(`Event from rust:\n\t${logs.verb}`);
         let event: Required<IEventData>;
-        if (typeof data === 'string') {
+        if (typeof data === 'string' || data instanceof ArrayBuffer) {
             try {
-                event = JSON.parse(data);
+                event = decode(data);
             } catch (e) {
-                const msg: string = `Failed to parse rust event data due error: ${e}.\nExpecting type (JSON string): { [type: string]: string | undefined }, got: ${data}`;
+                const msg: string = `Failed to parse rust event data due error: ${e}.\nExpecting type (JSON string or MessagePack): { [type: string]: string | undefined }, got: ${logs.debug}`;
                 this.debug().emit.error(msg);
                 this.logger.error(msg);
                 return;
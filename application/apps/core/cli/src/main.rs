@@ -0,0 +1,131 @@
+//! Runs operations of elrust session without Node. Each `CallbackEvent` of
+//! the session is printed into stdout as a JSON line; logs go into stderr
+//! (see `RUST_LOG`).
+//!
+//! Ctrl-C aborts the running operation; the second Ctrl-C stops the session.
+
+use clap::{Parser, Subcommand};
+use log::{debug, error};
+use session::{
+    events::{CallbackEvent, ComputationError},
+    session::Session,
+};
+use std::process::ExitCode;
+use tokio::signal;
+use uuid::Uuid;
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "elrust-cli",
+    version,
+    about = "Runs operations of elrust session"
+)]
+struct Cli {
+    /// UUID of session; a random one is used if it isn't given
+    #[arg(long)]
+    session: Option<Uuid>,
+    #[command(subcommand)]
+    operation: Operation,
+}
+
+#[derive(Subcommand, Debug)]
+enum Operation {
+    /// Sleeps given number of milliseconds
+    Sleep {
+        #[arg(long, default_value_t = 1000)]
+        ms: u64,
+    },
+    /// Calls "sum" and "find" functions of external library
+    ExternalLib {
+        /// Path to dynamic library
+        #[arg(long)]
+        path: String,
+        #[arg(long, default_value_t = 0)]
+        a: u64,
+        #[arg(long, default_value_t = 0)]
+        b: u64,
+        /// Lines to search in; can be repeated
+        #[arg(long = "line")]
+        lines: Vec<String>,
+    },
+}
+
+impl Operation {
+    fn start(self, session: &Session) -> Result<Uuid, ComputationError> {
+        match self {
+            Operation::Sleep { ms } => session.sleep(None, ms),
+            Operation::ExternalLib { path, a, b, lines } => {
+                session.external_call_lib(None, path, a, b, lines)
+            }
+        }
+    }
+}
+
+fn print(event: &CallbackEvent) {
+    match serde_json::to_string(event) {
+        Ok(json) => println!("{json}"),
+        Err(err) => error!("Fail to serialize event {event}: {err}"),
+    }
+}
+
+async fn run(cli: Cli) -> Result<bool, ComputationError> {
+    let (session, mut rx_callback_events) =
+        Session::new(cli.session.unwrap_or_else(Uuid::new_v4)).await?;
+    let operation = cli.operation.start(&session)?;
+    debug!("Operation {operation} is started");
+    let mut succeeded = true;
+    let mut aborted = false;
+    let mut stopping = false;
+    loop {
+        tokio::select! {
+            event = rx_callback_events.recv() => {
+                let Some(event) = event else {
+                    break;
+                };
+                print(&event);
+                let finished = match &event {
+                    CallbackEvent::OperationDone(done) => done.uuid == operation,
+                    CallbackEvent::OperationError { uuid, .. } if *uuid == operation => {
+                        succeeded = false;
+                        true
+                    }
+                    CallbackEvent::SessionError(_) => {
+                        succeeded = false;
+                        true
+                    }
+                    _ => false,
+                };
+                if finished && !stopping {
+                    stopping = true;
+                    session.stop(None).await?;
+                }
+            },
+            _ = signal::ctrl_c(), if !stopping => {
+                if aborted {
+                    debug!("Stopping session");
+                    stopping = true;
+                    session.stop(None).await?;
+                } else {
+                    debug!("Aborting operation {operation}");
+                    aborted = true;
+                    session.abort(None, operation)?;
+                }
+            },
+        }
+    }
+    print(&CallbackEvent::SessionDestroyed);
+    Ok(succeeded && !aborted)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    env_logger::init();
+    match run(Cli::parse()).await {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,38 @@
+/// Builds regular expression from filter. Used by backend for searching and
+/// by wasm utils for checking filters on client, so both always agree.
+pub fn filter_as_regex(filter: String, ignore_case: bool, whole_word: bool, regex: bool) -> String {
+    let word_marker = if whole_word { "\\b" } else { "" };
+    let ignore_case_start = if ignore_case { "(?i)" } else { "" };
+    let ignore_case_end = if ignore_case { "(?-i)" } else { "" };
+    let subject = if regex {
+        filter
+    } else {
+        regex::escape(&filter)
+    };
+    format!("{ignore_case_start}{word_marker}{subject}{word_marker}{ignore_case_end}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::filter_as_regex;
+
+    #[test]
+    fn plain_filter_is_escaped() {
+        assert_eq!(
+            filter_as_regex(String::from("a.b"), false, false, false),
+            r"a\.b"
+        );
+        assert_eq!(
+            filter_as_regex(String::from("a.b"), false, false, true),
+            "a.b"
+        );
+    }
+
+    #[test]
+    fn flags() {
+        assert_eq!(
+            filter_as_regex(String::from("err"), true, true, true),
+            r"(?i)\berr\b(?-i)"
+        );
+    }
+}
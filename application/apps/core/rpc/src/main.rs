@@ -0,0 +1,94 @@
+//! Headless server exposing elrust sessions, unbound jobs and progress tracking
+//! over JSON-RPC 2.0. Messages are newline delimited JSON; transport is
+//! stdin/stdout or a Unix socket. Logs go into stderr (see `RUST_LOG`).
+//!
+//! Events of sessions are pushed as `session.event` notifications with
+//! `{ "session": uuid, "event": CallbackEvent }`; lifecycle transitions of
+//! operations and jobs are pushed as `tracker.event` notifications.
+//!
+//! Unbound jobs are called as `jobs.<alias>` (see `UnboundCommand::ALIAS`) with
+//! `{ "id": number, "replace"?: bool, "partials"?: bool, ...arguments of job }`.
+//! With `partials: true` intermediate results are pushed as `jobs.partial`
+//! notifications with `{ "id": number, "partial": results }`.
+
+mod protocol;
+mod server;
+
+use clap::Parser;
+use log::{debug, error};
+use server::Server;
+use std::{path::PathBuf, process::ExitCode, sync::Arc};
+use tokio::{io, signal};
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "elrust-rpc",
+    version,
+    about = "Serves elrust session API over JSON-RPC"
+)]
+struct Cli {
+    /// Listen on Unix socket instead of stdin/stdout
+    #[arg(long)]
+    socket: Option<PathBuf>,
+}
+
+#[cfg(unix)]
+async fn listen(server: Arc<Server>, path: PathBuf) -> std::io::Result<()> {
+    use tokio::net::UnixListener;
+    if path.exists() {
+        std::fs::remove_file(&path)?;
+    }
+    let listener = UnixListener::bind(&path)?;
+    debug!("Listening on {path:?}");
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let server = server.clone();
+        tokio::spawn(async move {
+            debug!("Client is connected");
+            let (reader, writer) = stream.into_split();
+            server.serve(reader, writer).await;
+            debug!("Client is disconnected");
+        });
+    }
+}
+
+#[cfg(not(unix))]
+async fn listen(_server: Arc<Server>, _path: PathBuf) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "Unix sockets aren't supported on this platform",
+    ))
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    env_logger::init();
+    let cli = Cli::parse();
+    let server = match Server::start().await {
+        Ok(server) => server,
+        Err(err) => {
+            eprintln!("Fail to start server: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let code = tokio::select! {
+        res = async {
+            match cli.socket {
+                Some(path) => listen(server.clone(), path).await,
+                None => {
+                    server.serve(io::stdin(), io::stdout()).await;
+                    Ok(())
+                }
+            }
+        } => match res {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                error!("Server is stopped with error: {err}");
+                ExitCode::FAILURE
+            }
+        },
+        _ = signal::ctrl_c() => ExitCode::SUCCESS,
+    };
+    server.shutdown().await;
+    code
+}
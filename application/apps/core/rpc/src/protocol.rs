@@ -0,0 +1,113 @@
+//! JSON-RPC 2.0 messages. Each message is a single line of JSON.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use session::events::ComputationError;
+
+pub const VERSION: &str = "2.0";
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+/// Error of computation; `data` has the original `ComputationError`
+pub const COMPUTATION_ERROR: i64 = -32000;
+
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    pub jsonrpc: String,
+    /// Requests without id are notifications; no response is sent for them
+    #[serde(default)]
+    pub id: Option<Value>,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    pub fn new<S: Into<String>>(code: i64, message: S) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    pub fn invalid_params(err: serde_json::Error) -> Self {
+        Self::new(INVALID_PARAMS, format!("Invalid params: {err}"))
+    }
+}
+
+impl From<ComputationError> for RpcError {
+    /// `InvalidArgs` (e.g. wrong arguments of job) is reported as invalid params
+    fn from(err: ComputationError) -> Self {
+        Self {
+            code: match err {
+                ComputationError::InvalidArgs(_) => INVALID_PARAMS,
+                _ => COMPUTATION_ERROR,
+            },
+            message: err.to_string(),
+            data: serde_json::to_value(&err).ok(),
+        }
+    }
+}
+
+impl From<serde_json::Error> for RpcError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::new(
+            COMPUTATION_ERROR,
+            format!("Fail to serialize results: {err}"),
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Response {
+    pub jsonrpc: &'static str,
+    pub id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+impl Response {
+    pub fn new(id: Value, result: Result<Value, RpcError>) -> Self {
+        let (result, error) = match result {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self {
+            jsonrpc: VERSION,
+            id,
+            result,
+            error,
+        }
+    }
+}
+
+/// Message pushed by server without request
+#[derive(Debug, Serialize)]
+pub struct Notification<T: Serialize> {
+    pub jsonrpc: &'static str,
+    pub method: &'static str,
+    pub params: T,
+}
+
+impl<T: Serialize> Notification<T> {
+    pub fn new(method: &'static str, params: T) -> Self {
+        Self {
+            jsonrpc: VERSION,
+            method,
+            params,
+        }
+    }
+}
@@ -0,0 +1,514 @@
+use crate::protocol::{
+    Notification, Request, Response, RpcError, INVALID_REQUEST, METHOD_NOT_FOUND, PARSE_ERROR,
+    VERSION,
+};
+use log::{debug, error, warn};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use session::{
+    events::{CallbackEvent, ComputationError},
+    progress::{run_tracking, ProgressTrackerAPI},
+    session::Session,
+    unbound::{
+        api::UnboundSessionAPI,
+        commands::{CommandOutcome, OnDuplicate, ALIASES},
+        UnboundSession,
+    },
+};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader},
+    select,
+    sync::{broadcast, mpsc::unbounded_channel},
+};
+use uuid::Uuid;
+
+/// Max number of notifications waiting to be written for a slow client
+const NOTIFICATIONS_CAPACITY: usize = 4096;
+
+#[derive(Deserialize)]
+struct SessionParams {
+    session: Uuid,
+}
+
+#[derive(Deserialize)]
+struct CreateParams {
+    #[serde(default)]
+    session: Option<Uuid>,
+}
+
+#[derive(Deserialize)]
+struct AbortParams {
+    session: Uuid,
+    #[serde(default)]
+    operation: Option<Uuid>,
+    target: Uuid,
+}
+
+#[derive(Deserialize)]
+struct SleepParams {
+    session: Uuid,
+    #[serde(default)]
+    operation: Option<Uuid>,
+    ms: u64,
+}
+
+#[derive(Deserialize)]
+struct ExternalCallLibParams {
+    session: Uuid,
+    #[serde(default)]
+    operation: Option<Uuid>,
+    path: String,
+    a: u64,
+    b: u64,
+    lines: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct SetDebugParams {
+    session: Uuid,
+    debug: bool,
+}
+
+#[derive(Deserialize)]
+struct JobIdParams {
+    id: u64,
+}
+
+/// Id of job and arguments of job on the same level. With `replace: true` a
+/// running or queued job with the same id is cancelled instead of rejecting
+/// the new job. With `partials: true` intermediate results of job are pushed
+/// as `jobs.partial` notifications
+#[derive(Deserialize)]
+struct JobParams {
+    id: u64,
+    #[serde(default)]
+    replace: bool,
+    #[serde(default)]
+    partials: bool,
+    #[serde(flatten)]
+    args: Value,
+}
+
+#[derive(Serialize)]
+struct JobPartial {
+    id: u64,
+    partial: Value,
+}
+
+/// Entry of registry of sessions
+enum Slot {
+    /// Session is being created; its uuid is already taken
+    Reserved,
+    /// Created session along with the number of its creation. The number tells
+    /// apart sessions, which reuse the same uuid one after another
+    Ready(u64, Session),
+}
+
+#[derive(Serialize)]
+struct SessionEvent<'a> {
+    session: Uuid,
+    event: &'a CallbackEvent,
+}
+
+fn on_duplicate(replace: bool) -> OnDuplicate {
+    if replace {
+        OnDuplicate::Replace
+    } else {
+        OnDuplicate::Reject
+    }
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params).map_err(RpcError::invalid_params)
+}
+
+/// Shares sessions, unbound jobs and progress tracker between all clients.
+/// Events of sessions and tracker are pushed to all clients as notifications
+pub struct Server {
+    sessions: Mutex<HashMap<Uuid, Slot>>,
+    created: AtomicU64,
+    jobs: UnboundSessionAPI,
+    tracker: ProgressTrackerAPI,
+    notifications: broadcast::Sender<String>,
+}
+
+impl Server {
+    pub async fn start() -> Result<Arc<Self>, ComputationError> {
+        let (tracker, rx_tracker) = ProgressTrackerAPI::new();
+        let mut lifecycle = run_tracking(rx_tracker).await?;
+        let (mut unbound, jobs) = UnboundSession::new();
+        unbound.init().await?;
+        let (notifications, _) = broadcast::channel(NOTIFICATIONS_CAPACITY);
+        let server = Arc::new(Self {
+            sessions: Mutex::new(HashMap::new()),
+            created: AtomicU64::new(0),
+            jobs,
+            tracker,
+            notifications,
+        });
+        let tracking = server.clone();
+        tokio::spawn(async move {
+            while let Some(transition) = lifecycle.recv().await {
+                tracking.notify("tracker.event", &transition);
+            }
+            debug!("Tracking is finished");
+        });
+        Ok(server)
+    }
+
+    fn notify<T: Serialize>(&self, method: &'static str, params: T) {
+        match serde_json::to_string(&Notification::new(method, params)) {
+            // Error means nobody is connected; that's fine
+            Ok(line) => {
+                let _ = self.notifications.send(line);
+            }
+            Err(err) => error!("Fail to serialize notification {method}: {err}"),
+        }
+    }
+
+    fn sessions(&self) -> Result<MutexGuard<'_, HashMap<Uuid, Slot>>, RpcError> {
+        self.sessions.lock().map_err(|e| {
+            ComputationError::Communication(format!("Cannot access sessions: {e}")).into()
+        })
+    }
+
+    fn with_session<T, F>(&self, uuid: Uuid, f: F) -> Result<T, RpcError>
+    where
+        F: FnOnce(&Session) -> Result<T, ComputationError>,
+    {
+        let sessions = self.sessions()?;
+        match sessions.get(&uuid) {
+            Some(Slot::Ready(_, session)) => Ok(f(session)?),
+            _ => Err(ComputationError::SessionUnavailable.into()),
+        }
+    }
+
+    async fn create_session(self: &Arc<Self>, uuid: Option<Uuid>) -> Result<Uuid, RpcError> {
+        let uuid = uuid.unwrap_or_else(Uuid::new_v4);
+        {
+            // Uuid is reserved before session is created, so concurrent requests
+            // with the same uuid cannot both pass the check
+            let mut sessions = self.sessions()?;
+            if sessions.contains_key(&uuid) {
+                return Err(ComputationError::InvalidArgs(format!(
+                    "Session {uuid} already exists"
+                ))
+                .into());
+            }
+            sessions.insert(uuid, Slot::Reserved);
+        }
+        let (session, mut rx_callback_events) = match Session::new(uuid).await {
+            Ok(created) => created,
+            Err(err) => {
+                if let Ok(mut sessions) = self.sessions.lock() {
+                    sessions.remove(&uuid);
+                }
+                return Err(err.into());
+            }
+        };
+        let generation = self.created.fetch_add(1, Ordering::SeqCst);
+        let rejected = match self.sessions()?.get_mut(&uuid) {
+            Some(slot @ Slot::Reserved) => {
+                *slot = Slot::Ready(generation, session);
+                None
+            }
+            // Reservation is dropped by shutdown of server
+            _ => Some(session),
+        };
+        if let Some(session) = rejected {
+            if let Err(err) = session.stop(None).await {
+                error!("Fail to stop session {uuid}: {err}");
+            }
+            return Err(ComputationError::SessionUnavailable.into());
+        }
+        let server = self.clone();
+        tokio::spawn(async move {
+            while let Some(event) = rx_callback_events.recv().await {
+                server.notify(
+                    "session.event",
+                    SessionEvent {
+                        session: uuid,
+                        event: &event,
+                    },
+                );
+            }
+            server.notify(
+                "session.event",
+                SessionEvent {
+                    session: uuid,
+                    event: &CallbackEvent::SessionDestroyed,
+                },
+            );
+            if let Ok(mut sessions) = server.sessions.lock() {
+                // Session with the same uuid could be created after this one
+                // has been destroyed; it shouldn't be removed
+                if matches!(sessions.get(&uuid), Some(Slot::Ready(n, _)) if *n == generation) {
+                    sessions.remove(&uuid);
+                }
+            }
+        });
+        Ok(uuid)
+    }
+
+    async fn destroy_session(&self, uuid: Uuid) -> Result<(), RpcError> {
+        let session = {
+            let mut sessions = self.sessions()?;
+            match sessions.remove(&uuid) {
+                Some(Slot::Ready(_, session)) => session,
+                Some(Slot::Reserved) => {
+                    sessions.insert(uuid, Slot::Reserved);
+                    return Err(ComputationError::SessionUnavailable.into());
+                }
+                None => return Err(ComputationError::SessionUnavailable.into()),
+            }
+        };
+        Ok(session.stop(None).await?)
+    }
+
+    /// Runs job with given alias (see `UnboundCommand::ALIAS`); arguments of
+    /// job are taken from params
+    async fn job(self: &Arc<Self>, alias: &str, params: Value) -> Result<Value, RpcError> {
+        let JobParams {
+            id,
+            replace,
+            partials,
+            args,
+        } = parse_params(params)?;
+        let outcome = if partials {
+            let server = self.clone();
+            self.jobs
+                .run_by_alias_with_partials(
+                    id,
+                    alias,
+                    args,
+                    on_duplicate(replace),
+                    move |partial| {
+                        if let CommandOutcome::Partial(partial) = partial {
+                            server.notify("jobs.partial", JobPartial { id, partial });
+                        }
+                    },
+                )
+                .await?
+        } else {
+            self.jobs
+                .run_by_alias(id, alias, args, on_duplicate(replace))
+                .await?
+        };
+        Ok(serde_json::to_value(outcome)?)
+    }
+
+    async fn handle(self: &Arc<Self>, method: &str, params: Value) -> Result<Value, RpcError> {
+        match method {
+            "session.create" => {
+                let CreateParams { session } = parse_params(params)?;
+                Ok(serde_json::to_value(self.create_session(session).await?)?)
+            }
+            "session.destroy" => {
+                let SessionParams { session } = parse_params(params)?;
+                self.destroy_session(session).await?;
+                Ok(Value::Null)
+            }
+            "session.abort" => {
+                let AbortParams {
+                    session,
+                    operation,
+                    target,
+                } = parse_params(params)?;
+                let uuid = self.with_session(session, |s| s.abort(operation, target))?;
+                Ok(serde_json::to_value(uuid)?)
+            }
+            "session.sleep" => {
+                let SleepParams {
+                    session,
+                    operation,
+                    ms,
+                } = parse_params(params)?;
+                let uuid = self.with_session(session, |s| s.sleep(operation, ms))?;
+                Ok(serde_json::to_value(uuid)?)
+            }
+            "session.externalCallLib" => {
+                let ExternalCallLibParams {
+                    session,
+                    operation,
+                    path,
+                    a,
+                    b,
+                    lines,
+                } = parse_params(params)?;
+                let uuid = self.with_session(session, |s| {
+                    s.external_call_lib(operation, path, a, b, lines)
+                })?;
+                Ok(serde_json::to_value(uuid)?)
+            }
+            "session.setDebug" => {
+                let SetDebugParams { session, debug } = parse_params(params)?;
+                let state = self.with_session(session, |s| Ok(s.get_state()))?;
+                state
+                    .set_debug(debug)
+                    .await
+                    .map_err(ComputationError::NativeError)?;
+                Ok(Value::Null)
+            }
+            "session.getOperationsStat" => {
+                let SessionParams { session } = parse_params(params)?;
+                let tracker = self.with_session(session, |s| Ok(s.tracker.clone()))?;
+                let stat = tracker
+                    .get_operations_stat()
+                    .await
+                    .map_err(ComputationError::NativeError)?;
+                Ok(Value::String(stat))
+            }
+            "jobs.abort" => {
+                let JobIdParams { id } = parse_params(params)?;
+                self.jobs.cancel_job(&id).await?;
+                Ok(Value::Null)
+            }
+            "jobs.cacheStats" => Ok(serde_json::to_value(self.jobs.cache_stats().await?)?),
+            "jobs.clearCache" => {
+                self.jobs.clear_cache()?;
+                Ok(Value::Null)
+            }
+            "tracker.stats" => Ok(Value::String(self.tracker.content().await?)),
+            _ => match method.strip_prefix("jobs.") {
+                Some(alias) if ALIASES.contains(&alias) => self.job(alias, params).await,
+                _ => Err(RpcError::new(
+                    METHOD_NOT_FOUND,
+                    format!("Method \"{method}\" isn't found"),
+                )),
+            },
+        }
+    }
+
+    /// Processes a single line of input. Returns serialized response or None
+    /// if no response is expected.
+    async fn process(self: &Arc<Self>, line: &str) -> Option<String> {
+        let (id, result) = match serde_json::from_str::<Request>(line) {
+            Ok(request) if request.jsonrpc != VERSION => (
+                request.id,
+                Err(RpcError::new(
+                    INVALID_REQUEST,
+                    format!("Unsupported version of JSON-RPC: {}", request.jsonrpc),
+                )),
+            ),
+            Ok(request) => {
+                debug!("Request {}", request.method);
+                let result = self.handle(&request.method, request.params).await;
+                (request.id, result)
+            }
+            Err(err) => (
+                Some(Value::Null),
+                Err(RpcError::new(
+                    PARSE_ERROR,
+                    format!("Invalid request: {err}"),
+                )),
+            ),
+        };
+        let response = Response::new(id?, result);
+        match serde_json::to_string(&response) {
+            Ok(line) => Some(line),
+            Err(err) => {
+                error!("Fail to serialize response: {err}");
+                None
+            }
+        }
+    }
+
+    /// Serves one client until its input is closed. Requests are processed
+    /// concurrently; responses may come in a different order.
+    pub async fn serve<R, W>(self: &Arc<Self>, reader: R, mut writer: W)
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin + Send + 'static,
+    {
+        let (tx_responses, mut rx_responses) = unbounded_channel::<String>();
+        let mut notifications = self.notifications.subscribe();
+        let writing = tokio::spawn(async move {
+            loop {
+                let line = select! {
+                    line = rx_responses.recv() => match line {
+                        Some(line) => line,
+                        None => break,
+                    },
+                    line = notifications.recv() => match line {
+                        Ok(line) => line,
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            warn!("Client is too slow; {skipped} notification(s) are dropped");
+                            continue;
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                };
+                if let Err(err) = async {
+                    writer.write_all(line.as_bytes()).await?;
+                    writer.write_all(b"\n").await?;
+                    writer.flush().await
+                }
+                .await
+                {
+                    warn!("Fail to write into client: {err}");
+                    break;
+                }
+            }
+        });
+        let mut lines = BufReader::new(reader).lines();
+        loop {
+            match lines.next_line().await {
+                Ok(Some(line)) if line.trim().is_empty() => {}
+                Ok(Some(line)) => {
+                    let server = self.clone();
+                    let tx_responses = tx_responses.clone();
+                    tokio::spawn(async move {
+                        if let Some(response) = server.process(&line).await {
+                            let _ = tx_responses.send(response);
+                        }
+                    });
+                }
+                Ok(None) => break,
+                Err(err) => {
+                    warn!("Fail to read from client: {err}");
+                    break;
+                }
+            }
+        }
+        // Writing is finished as soon as all pending requests are answered
+        drop(tx_responses);
+        if let Err(err) = writing.await {
+            error!("Writing task is failed: {err}");
+        }
+    }
+
+    /// Stops all sessions, jobs and progress tracking
+    pub async fn shutdown(&self) {
+        let sessions: Vec<Session> = match self.sessions.lock() {
+            Ok(mut sessions) => sessions
+                .drain()
+                .filter_map(|(_, slot)| match slot {
+                    Slot::Ready(_, session) => Some(session),
+                    Slot::Reserved => None,
+                })
+                .collect(),
+            Err(err) => {
+                error!("Cannot access sessions: {err}");
+                Vec::new()
+            }
+        };
+        for session in sessions {
+            if let Err(err) = session.stop(None).await {
+                error!("Fail to stop session {}: {err}", session.get_uuid());
+            }
+        }
+        if let Err(err) = self.jobs.shutdown().await {
+            error!("Fail to shutdown jobs: {err}");
+        }
+        if let Err(err) = self.tracker.abort().await {
+            error!("Fail to abort tracker: {err}");
+        }
+    }
+}
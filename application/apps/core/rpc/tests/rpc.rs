@@ -0,0 +1,268 @@
+use serde_json::{json, Value};
+use std::{process::Stdio, time::Duration};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines},
+    process::{Child, ChildStdin, ChildStdout, Command},
+    time::timeout,
+};
+use uuid::Uuid;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Server running in a child process and talking over stdin/stdout
+struct Client {
+    child: Child,
+    stdin: ChildStdin,
+    lines: Lines<BufReader<ChildStdout>>,
+    /// Messages received while waiting for other ones
+    received: Vec<Value>,
+}
+
+impl Client {
+    fn start() -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_elrust-rpc"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .expect("server is started");
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+        Self {
+            child,
+            stdin,
+            lines: BufReader::new(stdout).lines(),
+            received: Vec::new(),
+        }
+    }
+
+    async fn send_line(&mut self, line: &str) {
+        self.stdin.write_all(line.as_bytes()).await.unwrap();
+        self.stdin.write_all(b"\n").await.unwrap();
+        self.stdin.flush().await.unwrap();
+    }
+
+    async fn send(&mut self, id: u64, method: &str, params: Value) {
+        let request = json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params});
+        self.send_line(&request.to_string()).await;
+    }
+
+    /// Waits for a message matching `predicate`; other messages are kept
+    async fn wait<F: Fn(&Value) -> bool>(&mut self, predicate: F) -> Value {
+        if let Some(pos) = self.received.iter().position(&predicate) {
+            return self.received.remove(pos);
+        }
+        loop {
+            let line = timeout(TIMEOUT, self.lines.next_line())
+                .await
+                .expect("timeout while waiting for message")
+                .unwrap()
+                .expect("server is closed");
+            let message: Value = serde_json::from_str(&line).expect("message is valid JSON");
+            if predicate(&message) {
+                return message;
+            }
+            self.received.push(message);
+        }
+    }
+
+    async fn response(&mut self, id: u64) -> Value {
+        self.wait(|message| message["id"] == json!(id)).await
+    }
+
+    async fn call(&mut self, id: u64, method: &str, params: Value) -> Value {
+        self.send(id, method, params).await;
+        self.response(id).await
+    }
+
+    async fn close(mut self) {
+        drop(self.stdin);
+        timeout(TIMEOUT, self.child.wait())
+            .await
+            .expect("server is stopped in time")
+            .unwrap();
+    }
+}
+
+fn error_code(response: &Value) -> Option<i64> {
+    response["error"]["code"].as_i64()
+}
+
+#[tokio::test]
+async fn protocol_errors() {
+    let mut client = Client::start();
+    client.send_line("{not json").await;
+    let response = client.wait(|message| message["id"].is_null()).await;
+    assert_eq!(error_code(&response), Some(-32700));
+    client
+        .send_line(r#"{"jsonrpc": "1.0", "id": 1, "method": "tracker.stats"}"#)
+        .await;
+    assert_eq!(error_code(&client.response(1).await), Some(-32600));
+    let response = client.call(2, "unknown.method", Value::Null).await;
+    assert_eq!(error_code(&response), Some(-32601));
+    let response = client.call(5, "jobs.unknown", json!({"id": 1})).await;
+    assert_eq!(error_code(&response), Some(-32601));
+    let response = client
+        .call(3, "jobs.cancelTest", json!({"id": 1, "custom_arg_a": "a"}))
+        .await;
+    assert_eq!(error_code(&response), Some(-32602));
+    // Notification (no id) gets no response
+    client
+        .send_line(r#"{"jsonrpc": "2.0", "method": "jobs.clearCache"}"#)
+        .await;
+    let response = client.call(4, "tracker.stats", Value::Null).await;
+    assert!(response["result"].is_string());
+    assert_eq!(response["jsonrpc"], json!("2.0"));
+    // Only notifications of server are left
+    assert!(client
+        .received
+        .iter()
+        .all(|message| message.get("id").is_none()));
+    client.close().await;
+}
+
+#[tokio::test]
+async fn jobs() {
+    let mut client = Client::start();
+    let response = client
+        .call(
+            1,
+            "jobs.cancelTest",
+            json!({"id": 1, "custom_arg_a": 1, "custom_arg_b": 2}),
+        )
+        .await;
+    assert_eq!(response["result"], json!({"Finished": 3}));
+    let response = client
+        .call(
+            2,
+            "jobs.getRegexError",
+            json!({"id": 2, "filter": {"value": "(", "is_regex": true, "ignore_case": false, "is_word": false}}),
+        )
+        .await;
+    assert!(response["result"]["Finished"].is_string());
+    let response = client
+        .call(3, "jobs.getSerialPortsList", json!({"id": 3}))
+        .await;
+    assert!(response["result"]["Finished"].is_array());
+    client.close().await;
+}
+
+#[tokio::test]
+async fn job_partials() {
+    let folder = std::env::temp_dir().join(Uuid::new_v4().to_string());
+    std::fs::create_dir(&folder).unwrap();
+    // Intermediate results are delivered with batches of 500 entities
+    for n in 0..501 {
+        std::fs::write(folder.join(format!("{n}.txt")), "").unwrap();
+    }
+    let mut client = Client::start();
+    let response = client
+        .call(
+            1,
+            "jobs.getFolderContent",
+            json!({
+                "id": 5,
+                "partials": true,
+                "paths": [folder],
+                "depth": 1,
+                "max_len": 1000,
+                "include_files": true,
+                "include_folders": false
+            }),
+        )
+        .await;
+    assert_eq!(
+        response["result"]["Finished"]["list"]
+            .as_array()
+            .map(Vec::len),
+        Some(501),
+        "{response}"
+    );
+    let partial = client
+        .wait(|message| message["method"] == json!("jobs.partial"))
+        .await;
+    assert_eq!(partial["params"]["id"], json!(5));
+    assert_eq!(
+        partial["params"]["partial"]["list"]
+            .as_array()
+            .map(Vec::len),
+        Some(500)
+    );
+    client.close().await;
+    std::fs::remove_dir_all(folder).unwrap();
+}
+
+#[tokio::test]
+async fn replace_job() {
+    let mut client = Client::start();
+    client
+        .send(
+            1,
+            "jobs.cancelTest",
+            json!({"id": 7, "custom_arg_a": 1, "custom_arg_b": 1}),
+        )
+        .await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let duplicate = client
+        .call(
+            2,
+            "jobs.cancelTest",
+            json!({"id": 7, "custom_arg_a": 2, "custom_arg_b": 2}),
+        )
+        .await;
+    // Taken id is reported as invalid params
+    assert_eq!(error_code(&duplicate), Some(-32602));
+    let replacing = client
+        .call(
+            3,
+            "jobs.cancelTest",
+            json!({"id": 7, "replace": true, "custom_arg_a": 3, "custom_arg_b": 3}),
+        )
+        .await;
+    assert_eq!(replacing["result"], json!({"Finished": 6}));
+    assert_eq!(client.response(1).await["result"], json!("Cancelled"));
+    client.close().await;
+}
+
+#[tokio::test]
+async fn concurrent_create_with_same_uuid() {
+    let mut client = Client::start();
+    let uuid = Uuid::new_v4();
+    client
+        .send(1, "session.create", json!({"session": uuid}))
+        .await;
+    client
+        .send(2, "session.create", json!({"session": uuid}))
+        .await;
+    let first = client.response(1).await;
+    let second = client.response(2).await;
+    let created = [&first, &second]
+        .iter()
+        .filter(|response| response["result"] == json!(uuid))
+        .count();
+    assert_eq!(created, 1, "{first} / {second}");
+    assert!(error_code(&first).is_some() || error_code(&second).is_some());
+    // Same uuid can be reused after session is destroyed; the new session
+    // isn't evicted when the previous one is finished
+    let response = client
+        .call(3, "session.destroy", json!({"session": uuid}))
+        .await;
+    assert!(response["result"].is_null() && response.get("error").is_none());
+    client
+        .wait(|message| {
+            message["method"] == json!("session.event")
+                && message["params"]["session"] == json!(uuid)
+                && message["params"]["event"] == json!("SessionDestroyed")
+        })
+        .await;
+    let response = client
+        .call(4, "session.create", json!({"session": uuid}))
+        .await;
+    assert_eq!(response["result"], json!(uuid));
+    let response = client
+        .call(5, "session.sleep", json!({"session": uuid, "ms": 10}))
+        .await;
+    assert!(response["result"].is_string(), "{response}");
+    client.close().await;
+}
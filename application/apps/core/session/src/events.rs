@@ -9,12 +9,18 @@ pub struct Ticks {
     pub count: u64,
     pub state: Option<String>,
     pub total: Option<u64>,
+    /// Ticks per second. Calculated by the progress tracker based on the
+    /// timestamps of successive updates; never set by the reporter
+    pub throughput: Option<f64>,
+    /// Estimated time remaining in milliseconds. Calculated by the progress
+    /// tracker; available only if `total` is known
+    pub eta: Option<u64>,
 }
 
 impl Ticks {
     pub fn done(&self) -> bool {
         match self.total {
-            Some(total) => self.count == total,
+            Some(total) => self.count >= total,
             None => false,
         }
     }
@@ -24,6 +30,8 @@ impl Ticks {
             count: 0,
             state: None,
             total: None,
+            throughput: None,
+            eta: None,
         }
     }
 }
@@ -40,6 +48,8 @@ impl Progress {
             count,
             total,
             state,
+            throughput: None,
+            eta: None,
         })
     }
 }
@@ -62,13 +72,29 @@ pub enum IndexingProgress<T> {
     Finished,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Notification {
     pub severity: Severity,
     pub content: String,
     pub line: Option<usize>,
 }
 
+impl Notification {
+    pub fn new<S: Into<String>>(severity: Severity, content: S) -> Self {
+        Notification {
+            severity,
+            content: content.into(),
+            line: None,
+        }
+    }
+
+    /// Refers notification to the given line
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+}
+
 pub struct ProgressReporter<T> {
     update_channel: cc::Sender<std::result::Result<IndexingProgress<T>, Notification>>,
     processed_bytes: u64,
@@ -109,6 +135,10 @@ impl<T> ProgressReporter<T> {
 pub enum Severity {
     WARNING,
     ERROR,
+    /// Informational message; nothing is wrong
+    INFO,
+    /// Diagnostic details, which are useful for debugging only
+    DEBUG,
 }
 
 impl Severity {
@@ -116,11 +146,13 @@ impl Severity {
         match self {
             Severity::WARNING => "WARNING",
             Severity::ERROR => "ERROR",
+            Severity::INFO => "INFO",
+            Severity::DEBUG => "DEBUG",
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub enum NativeErrorKind {
     /// The file in question does not exist
     FileNotFound,
@@ -134,59 +166,163 @@ pub enum NativeErrorKind {
     ChannelError,
     Io,
     Grabber,
+    InvalidArgs,
+    InvalidData,
+}
+
+impl NativeErrorKind {
+    /// Stable numeric code of the kind of error. Codes are used on the client
+    /// side to classify and localise errors; never change or reuse them.
+    pub fn code(&self) -> u32 {
+        match self {
+            Self::FileNotFound => 1001,
+            Self::UnsupportedFileType => 1002,
+            Self::ComputationFailed => 1003,
+            Self::Configuration => 1004,
+            Self::Interrupted => 1005,
+            Self::OperationSearch => 1006,
+            Self::NotYetImplemented => 1007,
+            Self::ChannelError => 1008,
+            Self::Io => 1009,
+            Self::Grabber => 1010,
+            Self::InvalidArgs => 1011,
+            Self::InvalidData => 1012,
+        }
+    }
+}
+
+/// Describes in which scope an error has happened. Errors collect context
+/// while they are passed up from the place of failure.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum ErrorContext {
+    Operation(Uuid),
+    Session(Uuid),
+    File(String),
+}
+
+/// Place in the source code where an error has been created
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl From<&std::panic::Location<'_>> for SourceLocation {
+    fn from(location: &std::panic::Location<'_>) -> Self {
+        SourceLocation {
+            file: location.file().to_owned(),
+            line: location.line(),
+            column: location.column(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct NativeError {
     pub severity: Severity,
     pub kind: NativeErrorKind,
+    /// Stable numeric code of error; see `NativeErrorKind::code` and `ComputationError::code`
+    #[serde(default)]
+    pub code: u32,
     pub message: Option<String>,
+    /// Scopes of error from the most specific to the most general one
+    #[serde(default)]
+    pub context: Vec<ErrorContext>,
+    /// Descriptions of underlying errors
+    #[serde(default)]
+    pub causes: Vec<String>,
+    #[serde(default)]
+    pub location: Option<SourceLocation>,
+    /// Original error if this error has been converted from `ComputationError`
+    #[serde(default)]
+    pub origin: Option<Box<ComputationError>>,
 }
 
 impl NativeError {
-    pub fn channel(msg: &str) -> Self {
+    #[track_caller]
+    pub fn new<S: Into<String>>(severity: Severity, kind: NativeErrorKind, message: S) -> Self {
         NativeError {
-            severity: Severity::ERROR,
-            kind: NativeErrorKind::ChannelError,
-            message: Some(String::from(msg)),
+            severity,
+            code: kind.code(),
+            kind,
+            message: Some(message.into()),
+            context: vec![],
+            causes: vec![],
+            location: Some(std::panic::Location::caller().into()),
+            origin: None,
         }
     }
+
+    #[track_caller]
+    pub fn channel(msg: &str) -> Self {
+        NativeError::new(Severity::ERROR, NativeErrorKind::ChannelError, msg)
+    }
+
+    pub fn with_operation(mut self, uuid: Uuid) -> Self {
+        self.context.push(ErrorContext::Operation(uuid));
+        self
+    }
+
+    pub fn with_session(mut self, uuid: Uuid) -> Self {
+        self.context.push(ErrorContext::Session(uuid));
+        self
+    }
+
+    pub fn with_file<S: Into<String>>(mut self, filename: S) -> Self {
+        self.context.push(ErrorContext::File(filename.into()));
+        self
+    }
+
+    pub fn caused_by<E: std::fmt::Display>(mut self, cause: E) -> Self {
+        self.causes.push(cause.to_string());
+        self
+    }
 }
 
 impl From<ComputationError> for NativeError {
+    #[track_caller]
     fn from(err: ComputationError) -> Self {
+        if let ComputationError::NativeError(err) = err {
+            return err;
+        }
         NativeError {
-            severity: Severity::ERROR,
-            kind: NativeErrorKind::Io,
-            message: Some(err.to_string()),
+            code: err.code(),
+            origin: Some(Box::new(err.clone())),
+            ..NativeError::new(Severity::ERROR, err.kind(), err.to_string())
         }
     }
 }
 
 impl From<std::io::Error> for NativeError {
+    #[track_caller]
     fn from(err: std::io::Error) -> Self {
-        NativeError {
-            severity: Severity::ERROR,
-            kind: NativeErrorKind::Io,
-            message: Some(err.to_string()),
-        }
+        let kind = if err.kind() == std::io::ErrorKind::NotFound {
+            NativeErrorKind::FileNotFound
+        } else {
+            NativeErrorKind::Io
+        };
+        NativeError::new(Severity::ERROR, kind, err.to_string())
     }
 }
 
 impl From<tokio::sync::mpsc::error::SendError<CallbackEvent>> for NativeError {
+    #[track_caller]
     fn from(err: tokio::sync::mpsc::error::SendError<CallbackEvent>) -> Self {
-        NativeError {
-            severity: Severity::ERROR,
-            kind: NativeErrorKind::ComputationFailed,
-            message: Some(format!("Callback channel is broken: {err}")),
-        }
+        NativeError::new(
+            Severity::ERROR,
+            NativeErrorKind::ComputationFailed,
+            format!("Callback channel is broken: {err}"),
+        )
     }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct OperationDone {
     pub uuid: Uuid,
-    pub result: Option<String>,
+    /// Results of operation. Kept as a value (not as a serialized string), so
+    /// they are encoded with the encoding of the whole event
+    pub result: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -203,6 +339,13 @@ pub enum CallbackEvent {
      * >> Kind: repeated
      */
     OperationError { uuid: Uuid, error: NativeError },
+    /**
+     * Non-fatal diagnostic message in the scope of session (for example
+     * "file was truncated, reindexing"). Doesn't affect running operations
+     * >> Scope: session, async operation
+     * >> Kind: repeated
+     */
+    SessionNotification(Notification),
     /**
      * Operations is created; task is spawned.
      * This even is triggered always
@@ -242,6 +385,12 @@ impl std::fmt::Display for CallbackEvent {
             Self::OperationError { uuid, error } => {
                 write!(f, "OperationError: {uuid}: {error:?}")
             }
+            Self::SessionNotification(notification) => write!(
+                f,
+                "SessionNotification: {}: {}",
+                notification.severity.as_str(),
+                notification.content
+            ),
             Self::OperationStarted(uuid) => write!(f, "OperationStarted: {uuid}"),
             Self::OperationProcessing(uuid) => write!(f, "OperationProcessing: {uuid}"),
             Self::OperationDone(info) => write!(f, "OperationDone: {}", info.uuid),
@@ -282,7 +431,7 @@ impl LifecycleTransition {
     }
 }
 
-#[derive(Error, Debug, Serialize)]
+#[derive(Error, Debug, Serialize, Deserialize, Clone)]
 pub enum ComputationError {
     #[error("Destination path should be defined to stream from MassageProducer")]
     DestinationPath,
@@ -310,4 +459,163 @@ pub enum ComputationError {
     Sde(String),
 }
 
+impl ComputationError {
+    /// Stable numeric code of error. Codes are used on the client side to
+    /// classify and localise errors; never change or reuse them.
+    pub fn code(&self) -> u32 {
+        match self {
+            Self::DestinationPath => 2001,
+            Self::Communication(_) => 2002,
+            Self::OperationNotSupported(_) => 2003,
+            Self::IoOperation(_) => 2004,
+            Self::InvalidData => 2005,
+            Self::InvalidArgs(_) => 2006,
+            Self::Process(_) => 2007,
+            Self::Protocol(_) => 2008,
+            Self::MultipleInitCall => 2009,
+            Self::SessionUnavailable => 2010,
+            Self::NativeError(err) => err.code,
+            Self::Sde(_) => 2012,
+        }
+    }
+
+    /// The closest kind of `NativeError` to this error
+    pub fn kind(&self) -> NativeErrorKind {
+        match self {
+            Self::DestinationPath | Self::MultipleInitCall => NativeErrorKind::Configuration,
+            Self::Communication(_) => NativeErrorKind::ChannelError,
+            Self::OperationNotSupported(_) => NativeErrorKind::NotYetImplemented,
+            Self::IoOperation(_) | Self::Sde(_) => NativeErrorKind::Io,
+            Self::InvalidData => NativeErrorKind::InvalidData,
+            Self::InvalidArgs(_) => NativeErrorKind::InvalidArgs,
+            Self::Process(_) | Self::Protocol(_) | Self::SessionUnavailable => {
+                NativeErrorKind::ComputationFailed
+            }
+            Self::NativeError(err) => err.kind.clone(),
+        }
+    }
+}
+
+impl From<NativeError> for ComputationError {
+    fn from(err: NativeError) -> Self {
+        match err.origin {
+            // Error has been converted from ComputationError and nothing has been added since
+            Some(origin) if err.context.is_empty() && err.causes.is_empty() => *origin,
+            _ => ComputationError::NativeError(err),
+        }
+    }
+}
+
 pub type SyncChannel<T> = (cc::Sender<T>, cc::Receiver<T>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn computation_errors() -> Vec<ComputationError> {
+        vec![
+            ComputationError::DestinationPath,
+            ComputationError::Communication(String::from("communication")),
+            ComputationError::OperationNotSupported(String::from("operation")),
+            ComputationError::IoOperation(String::from("io")),
+            ComputationError::InvalidData,
+            ComputationError::InvalidArgs(String::from("args")),
+            ComputationError::Process(String::from("process")),
+            ComputationError::Protocol(String::from("protocol")),
+            ComputationError::MultipleInitCall,
+            ComputationError::SessionUnavailable,
+            ComputationError::NativeError(NativeError::new(
+                Severity::WARNING,
+                NativeErrorKind::Grabber,
+                "native",
+            )),
+            ComputationError::Sde(String::from("sde")),
+        ]
+    }
+
+    fn as_json<T: Serialize>(value: &T) -> serde_json::Value {
+        serde_json::to_value(value).expect("error is serializable")
+    }
+
+    #[test]
+    fn computation_error_round_trip() {
+        for err in computation_errors() {
+            let native = NativeError::from(err.clone());
+            assert_eq!(native.code, err.code());
+            assert_eq!(native.kind, err.kind());
+            assert_eq!(as_json(&ComputationError::from(native)), as_json(&err));
+        }
+    }
+
+    #[test]
+    fn round_trip_survives_serialization() {
+        for err in computation_errors() {
+            let native: NativeError =
+                serde_json::from_value(as_json(&NativeError::from(err.clone())))
+                    .expect("native error is deserializable");
+            assert_eq!(as_json(&ComputationError::from(native)), as_json(&err));
+        }
+    }
+
+    #[test]
+    fn origin_is_dropped_with_context() {
+        let uuid = Uuid::new_v4();
+        let with_context = NativeError::from(ComputationError::InvalidData).with_operation(uuid);
+        match ComputationError::from(with_context) {
+            ComputationError::NativeError(native) => {
+                assert_eq!(native.context, vec![ErrorContext::Operation(uuid)]);
+                assert_eq!(native.code, 2005);
+            }
+            err => panic!("context is lost: {err:?}"),
+        }
+        let with_cause = NativeError::from(ComputationError::InvalidData).caused_by("broken");
+        match ComputationError::from(with_cause) {
+            ComputationError::NativeError(native) => {
+                assert_eq!(native.causes, vec![String::from("broken")]);
+            }
+            err => panic!("cause is lost: {err:?}"),
+        }
+    }
+
+    #[test]
+    fn stable_codes() {
+        let kinds = [
+            NativeErrorKind::FileNotFound,
+            NativeErrorKind::UnsupportedFileType,
+            NativeErrorKind::ComputationFailed,
+            NativeErrorKind::Configuration,
+            NativeErrorKind::Interrupted,
+            NativeErrorKind::OperationSearch,
+            NativeErrorKind::NotYetImplemented,
+            NativeErrorKind::ChannelError,
+            NativeErrorKind::Io,
+            NativeErrorKind::Grabber,
+            NativeErrorKind::InvalidArgs,
+            NativeErrorKind::InvalidData,
+        ];
+        assert_eq!(
+            kinds.iter().map(|kind| kind.code()).collect::<Vec<u32>>(),
+            (1001..=1012).collect::<Vec<u32>>()
+        );
+        // NativeError variant takes the code of the wrapped error instead of 2011
+        assert_eq!(
+            computation_errors()
+                .iter()
+                .map(|err| err.code())
+                .collect::<Vec<u32>>(),
+            vec![2001, 2002, 2003, 2004, 2005, 2006, 2007, 2008, 2009, 2010, 1010, 2012]
+        );
+    }
+
+    #[test]
+    fn location_of_caller() {
+        let (native, line) = (NativeError::channel("closed"), line!());
+        let location = native.location.expect("location is captured");
+        assert_eq!(location.file, file!());
+        assert_eq!(location.line, line);
+        let (converted, line) = (NativeError::from(ComputationError::InvalidData), line!());
+        let location = converted.location.expect("location is captured");
+        assert_eq!(location.file, file!());
+        assert_eq!(location.line, line);
+    }
+}
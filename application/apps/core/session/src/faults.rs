@@ -0,0 +1,114 @@
+//! Fault injection for the loops of session. Faults can be injected only with
+//! feature `fault-injection`; without it `Faults` is a no-op and costs nothing.
+//!
+//! Messages are counted per loop starting from 1. A fault is applied once, when
+//! the loop receives the message with the given number.
+
+use crate::events::NativeError;
+#[cfg(feature = "fault-injection")]
+use crate::events::{NativeErrorKind, Severity};
+#[cfg(feature = "fault-injection")]
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// Loop of session, which receives messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Loop {
+    /// `state::run`
+    State,
+    /// `tracker::run`
+    Tracker,
+    /// `operations::run`
+    Operations,
+}
+
+#[cfg(feature = "fault-injection")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fault {
+    /// Loop drops its channel and exits as if all senders were closed. The
+    /// received message isn't handled, so a pending response is never sent.
+    DropChannel,
+    /// Handling of the message is postponed for the given time. Works with
+    /// `tokio::time::pause`.
+    Delay(Duration),
+    /// Loop exits with an error without handling the message
+    Fail,
+}
+
+/// What a loop should do instead of handling the received message
+#[derive(Debug)]
+#[cfg_attr(not(feature = "fault-injection"), allow(dead_code))]
+pub(crate) enum Injected {
+    Drop,
+    Fail(NativeError),
+}
+
+#[cfg(feature = "fault-injection")]
+#[derive(Debug, Default)]
+struct Plan {
+    faults: HashMap<Loop, Vec<(usize, Fault)>>,
+    received: HashMap<Loop, usize>,
+}
+
+/// Faults planned for loops of one session (see `Session::with_faults`)
+#[derive(Debug, Clone, Default)]
+pub struct Faults {
+    #[cfg(feature = "fault-injection")]
+    plan: Arc<Mutex<Plan>>,
+}
+
+impl Faults {
+    /// Plans `fault` on the `nth` (starting from 1) message received by `target`
+    #[cfg(feature = "fault-injection")]
+    pub fn inject(self, target: Loop, nth: usize, fault: Fault) -> Self {
+        if let Ok(mut plan) = self.plan.lock() {
+            plan.faults.entry(target).or_default().push((nth, fault));
+        }
+        self
+    }
+
+    /// Number of messages received by `target` so far
+    #[cfg(feature = "fault-injection")]
+    pub fn received(&self, target: Loop) -> usize {
+        self.plan
+            .lock()
+            .map(|plan| plan.received.get(&target).copied().unwrap_or(0))
+            .unwrap_or(0)
+    }
+
+    /// Should be called by loop on each received message. Delays are applied
+    /// here; other faults are returned to be applied by the loop.
+    #[cfg(feature = "fault-injection")]
+    pub(crate) async fn hit(&self, target: Loop) -> Option<Injected> {
+        let fault = {
+            let mut plan = self.plan.lock().ok()?;
+            let received = plan.received.entry(target).or_default();
+            *received += 1;
+            let nth = *received;
+            let faults = plan.faults.get_mut(&target)?;
+            let pos = faults.iter().position(|(n, _)| *n == nth)?;
+            faults.remove(pos).1
+        };
+        log::debug!("Fault {fault:?} is injected into {target:?} loop");
+        match fault {
+            Fault::Delay(duration) => {
+                tokio::time::sleep(duration).await;
+                None
+            }
+            Fault::DropChannel => Some(Injected::Drop),
+            Fault::Fail => Some(Injected::Fail(NativeError::new(
+                Severity::ERROR,
+                NativeErrorKind::Io,
+                format!("Injected fault in {target:?} loop"),
+            ))),
+        }
+    }
+
+    #[cfg(not(feature = "fault-injection"))]
+    pub(crate) async fn hit(&self, _target: Loop) -> Option<Injected> {
+        None
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    events::{NativeError, NativeErrorKind, Severity},
+    events::{NativeError, NativeErrorKind, Notification, Severity},
     operations::{OperationAPI, OperationResult},
 };
 use libloading::{Library, Symbol};
@@ -13,24 +13,41 @@ pub async fn handle(
 ) -> OperationResult<(u64, Option<String>)> {
     // Cancellation token can be used to stop operation
     let _canceler = operation_api.cancellation_token();
+    // Library takes each entry as a single line; entries with line breaks are matched as a whole
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.contains(['\n', '\r']))
+        .for_each(|(n, _)| {
+            operation_api.notify(
+                Notification::new(Severity::WARNING, "Line contains line break(s)").at_line(n),
+            )
+        });
     unsafe {
-        let lib = Library::new(path).map_err(|e| NativeError {
-            severity: Severity::ERROR,
-            kind: NativeErrorKind::ComputationFailed,
-            message: Some(format!("Fail to load lib: {e}")),
+        let lib = Library::new(&path).map_err(|e| {
+            NativeError::new(
+                Severity::ERROR,
+                NativeErrorKind::ComputationFailed,
+                format!("Fail to load lib: {e}"),
+            )
+            .with_file(&path)
+            .caused_by(&e)
         })?;
-        let sum: Symbol<fn(u64, u64) -> u64> = lib.get(b"sum").map_err(|e| NativeError {
-            severity: Severity::ERROR,
-            kind: NativeErrorKind::ComputationFailed,
-            message: Some(format!("Fail to link \"sum\" func: {e}")),
+        let sum: Symbol<fn(u64, u64) -> u64> = lib.get(b"sum").map_err(|e| {
+            NativeError::new(
+                Severity::ERROR,
+                NativeErrorKind::ComputationFailed,
+                format!("Fail to link \"sum\" func: {e}"),
+            )
         })?;
         #[allow(clippy::type_complexity)]
-        let find: Symbol<fn(&[&str], &str) -> Option<String>> =
-            lib.get(b"find").map_err(|e| NativeError {
-                severity: Severity::ERROR,
-                kind: NativeErrorKind::ComputationFailed,
-                message: Some(format!("Fail to link \"find\" func: {e}")),
-            })?;
+        let find: Symbol<fn(&[&str], &str) -> Option<String>> = lib.get(b"find").map_err(|e| {
+            NativeError::new(
+                Severity::ERROR,
+                NativeErrorKind::ComputationFailed,
+                format!("Fail to link \"find\" func: {e}"),
+            )
+        })?;
         Ok(Some((sum(a, b), find(lines, "tw"))))
     }
 }
@@ -1,4 +1,5 @@
 pub mod events;
+pub mod faults;
 mod handlers;
 pub mod operations;
 pub mod paths;
@@ -1,7 +1,9 @@
 use crate::{
     events::{
-        CallbackEvent, ComputationError, NativeError, NativeErrorKind, OperationDone, Severity,
+        CallbackEvent, ComputationError, NativeError, NativeErrorKind, Notification, OperationDone,
+        Severity,
     },
+    faults::{Faults, Injected, Loop},
     handlers,
     state::SessionStateAPI,
     tracker::OperationTrackerAPI,
@@ -101,6 +103,7 @@ pub type OperationResult<T> = Result<Option<T>, NativeError>;
 pub struct OperationAPI {
     tx_callback_events: UnboundedSender<CallbackEvent>,
     operation_id: Uuid,
+    session_id: Uuid,
     state_api: SessionStateAPI,
     tracker_api: OperationTrackerAPI,
     // Used to force cancellation
@@ -115,11 +118,13 @@ impl OperationAPI {
         tracker_api: OperationTrackerAPI,
         tx_callback_events: UnboundedSender<CallbackEvent>,
         operation_id: Uuid,
+        session_id: Uuid,
         cancellation_token: CancellationToken,
     ) -> Self {
         OperationAPI {
             tx_callback_events,
             operation_id,
+            session_id,
             cancellation_token,
             done_token: CancellationToken::new(),
             state_api,
@@ -131,6 +136,13 @@ impl OperationAPI {
         self.operation_id
     }
 
+    /// Adds scope of operation and session to the error
+    pub fn scoped(&self, error: NativeError) -> NativeError {
+        error
+            .with_operation(self.operation_id)
+            .with_session(self.session_id)
+    }
+
     pub fn done_token(&self) -> CancellationToken {
         self.done_token.clone()
     }
@@ -150,6 +162,11 @@ impl OperationAPI {
         self.emit(CallbackEvent::OperationProcessing(self.id()));
     }
 
+    /// Surfaces non-fatal diagnostic message to the client
+    pub fn notify(&self, notification: Notification) {
+        self.emit(CallbackEvent::SessionNotification(notification));
+    }
+
     pub async fn finish<T>(&self, result: OperationResult<T>, alias: &str)
     where
         T: Serialize + std::fmt::Debug,
@@ -157,18 +174,18 @@ impl OperationAPI {
         let event = match result {
             Ok(result) => {
                 if let Some(result) = result.as_ref() {
-                    match serde_json::to_string(result) {
-                        Ok(serialized) => CallbackEvent::OperationDone(OperationDone {
+                    match serde_json::to_value(result) {
+                        Ok(value) => CallbackEvent::OperationDone(OperationDone {
                             uuid: self.operation_id,
-                            result: Some(serialized),
+                            result: Some(value),
                         }),
                         Err(err) => CallbackEvent::OperationError {
                             uuid: self.operation_id,
-                            error: NativeError {
-                                severity: Severity::ERROR,
-                                kind: NativeErrorKind::ComputationFailed,
-                                message: Some(format!("{err}")),
-                            },
+                            error: NativeError::new(
+                                Severity::ERROR,
+                                NativeErrorKind::ComputationFailed,
+                                format!("{err}"),
+                            ),
                         },
                     }
                 } else {
@@ -185,7 +202,7 @@ impl OperationAPI {
                 );
                 CallbackEvent::OperationError {
                     uuid: self.operation_id,
-                    error,
+                    error: self.scoped(error),
                 }
             }
         };
@@ -215,11 +232,11 @@ impl OperationAPI {
             )
             .await?;
         if !added {
-            return Err(NativeError {
-                severity: Severity::ERROR,
-                kind: NativeErrorKind::ComputationFailed,
-                message: Some(format!("Operation {} already exists", self.id())),
-            });
+            return Err(NativeError::new(
+                Severity::ERROR,
+                NativeErrorKind::ComputationFailed,
+                format!("Operation {} already exists", self.id()),
+            ));
         }
         let api = self.clone();
         let tracker = self.tracker_api.clone();
@@ -246,13 +263,13 @@ impl OperationAPI {
                                 .await;
                         } else {
                             api.finish::<OperationResult<()>>(
-                                Err(NativeError {
-                                    severity: Severity::WARNING,
-                                    kind: NativeErrorKind::Io,
-                                    message: Some(format!(
+                                Err(NativeError::new(
+                                    Severity::WARNING,
+                                    NativeErrorKind::Io,
+                                    format!(
                                         "Fail to cancel operation {target}; operation isn't found"
-                                    )),
-                                }),
+                                    ),
+                                )),
                                 operation_str,
                             )
                             .await;
@@ -260,13 +277,11 @@ impl OperationAPI {
                     }
                     Err(err) => {
                         api.finish::<OperationResult<()>>(
-                            Err(NativeError {
-                                severity: Severity::WARNING,
-                                kind: NativeErrorKind::Io,
-                                message: Some(format!(
-                                    "Fail to cancel operation {target}; error: {err:?}"
-                                )),
-                            }),
+                            Err(NativeError::new(
+                                Severity::WARNING,
+                                NativeErrorKind::Io,
+                                format!("Fail to cancel operation {target}; error: {err:?}"),
+                            )),
                             operation_str,
                         )
                         .await;
@@ -295,21 +310,33 @@ pub async fn run(
     state_api: SessionStateAPI,
     tracker_api: OperationTrackerAPI,
     tx_callback_events: UnboundedSender<CallbackEvent>,
+    session_id: Uuid,
+    faults: Faults,
 ) {
     debug!("task is started");
     while let Some(operation) = rx_operations.recv().await {
+        match faults.hit(Loop::Operations).await {
+            Some(Injected::Drop) => break,
+            Some(Injected::Fail(err)) => {
+                error!("Operations loop fails: {err:?}");
+                let _ = tx_callback_events.send(CallbackEvent::SessionError(err));
+                break;
+            }
+            None => {}
+        }
         if !matches!(operation.kind, OperationKind::End) {
             let operation_api = OperationAPI::new(
                 state_api.clone(),
                 tracker_api.clone(),
                 tx_callback_events.clone(),
                 operation.id,
+                session_id,
                 CancellationToken::new(),
             );
             if let Err(err) = operation_api.execute(operation).await {
                 operation_api.emit(CallbackEvent::OperationError {
                     uuid: operation_api.id(),
-                    error: err,
+                    error: operation_api.scoped(err),
                 });
             }
         } else {
@@ -3,7 +3,7 @@ use crate::{
     TRACKER_CHANNEL,
 };
 use log::{error, info};
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Instant};
 use tokio::{
     select,
     sync::{
@@ -105,6 +105,73 @@ impl ProgressTrackerAPI {
     }
 }
 
+/// Weight of the latest measurement in the smoothed throughput
+const THROUGHPUT_SMOOTHING: f64 = 0.3;
+
+/// Keeps the time and the count of the last reported ticks of an operation
+/// to estimate its throughput and the time remaining
+#[derive(Debug)]
+struct Estimation {
+    last: Instant,
+    count: u64,
+    throughput: Option<f64>,
+}
+
+impl Estimation {
+    fn new() -> Self {
+        Self::since(Instant::now(), 0)
+    }
+
+    /// Estimation starting from the given count; used if the first reported
+    /// ticks of operation aren't preceded by `Started`
+    fn since(last: Instant, count: u64) -> Self {
+        Self {
+            last,
+            count,
+            throughput: None,
+        }
+    }
+
+    /// Updates the estimation with the given ticks and writes the calculated
+    /// throughput and ETA into them
+    fn update(&mut self, ticks: &mut Ticks) {
+        self.update_at(ticks, Instant::now());
+    }
+
+    fn update_at(&mut self, ticks: &mut Ticks, now: Instant) {
+        if ticks.count < self.count {
+            // Counter has been reset by the operation; start from scratch
+            self.last = now;
+            self.count = ticks.count;
+            self.throughput = None;
+        } else if ticks.count > self.count {
+            let elapsed = now.duration_since(self.last).as_secs_f64();
+            if elapsed > 0.0 {
+                let current = (ticks.count - self.count) as f64 / elapsed;
+                self.throughput = Some(match self.throughput {
+                    Some(prev) => {
+                        prev * (1.0 - THROUGHPUT_SMOOTHING) + current * THROUGHPUT_SMOOTHING
+                    }
+                    None => current,
+                });
+                self.last = now;
+                self.count = ticks.count;
+            }
+        }
+        ticks.throughput = self.throughput;
+        ticks.eta = if ticks.done() {
+            Some(0)
+        } else {
+            match (ticks.total, self.throughput) {
+                (Some(total), Some(throughput)) if throughput > 0.0 => Some(
+                    (total.saturating_sub(ticks.count) as f64 / throughput * 1000.0).round() as u64,
+                ),
+                _ => None,
+            }
+        };
+    }
+}
+
 fn log_if_err(res: Result<(), SendError<LifecycleTransition>>) {
     if res.is_err() {
         error!("Fail to send event into lifecycle_events_channel. Channel is closed");
@@ -119,6 +186,7 @@ pub async fn run_tracking(
     mut command_rx: UnboundedReceiver<ProgressCommand>,
 ) -> Result<mpsc::Receiver<LifecycleTransition>, ComputationError> {
     let mut ongoing_operations: HashMap<Uuid, Ticks> = HashMap::new();
+    let mut estimations: HashMap<Uuid, Estimation> = HashMap::new();
     let lifecycle_events_channel = mpsc::channel(1);
 
     let mut lifecycle_events = {
@@ -152,15 +220,21 @@ pub async fn run_tracking(
                         Some(LifecycleTransition::Started { uuid, alias }) => {
                             info!("job {alias} ({uuid}) started");
                             ongoing_operations.insert(uuid, Ticks::new());
+                            estimations.insert(uuid, Estimation::new());
                             log_if_err(lifecycle_events_channel.0.send(LifecycleTransition::started(&uuid, &alias)).await);
                         }
                         Some(LifecycleTransition::Stopped(uuid)) => {
                             info!("job {uuid} stopped");
                             ongoing_operations.remove(&uuid);
+                            estimations.remove(&uuid);
                             log_if_err(lifecycle_events_channel.0.send(LifecycleTransition::Stopped(uuid)).await);
                         }
-                        Some(LifecycleTransition::Ticks {uuid, ticks}) => {
+                        Some(LifecycleTransition::Ticks {uuid, mut ticks}) => {
                             info!("job {uuid} reported progress: {ticks:?}");
+                            estimations
+                                .entry(uuid)
+                                .or_insert_with(|| Estimation::since(Instant::now(), ticks.count))
+                                .update(&mut ticks);
                             ongoing_operations.insert(uuid, ticks.clone());
                             log_if_err(lifecycle_events_channel.0.send(LifecycleTransition::ticks(&uuid, ticks)).await);
                         }
@@ -173,3 +247,84 @@ pub async fn run_tracking(
     });
     Ok(lifecycle_events_channel.1)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn ticks(count: u64, total: Option<u64>) -> Ticks {
+        Ticks {
+            count,
+            total,
+            ..Ticks::new()
+        }
+    }
+
+    #[test]
+    fn smoothed_throughput_and_eta() {
+        let start = Instant::now();
+        let mut estimation = Estimation::since(start, 0);
+        let mut first = ticks(100, Some(1000));
+        estimation.update_at(&mut first, start + Duration::from_secs(1));
+        assert_eq!(first.throughput, Some(100.0));
+        assert_eq!(first.eta, Some(9000));
+        let mut second = ticks(400, Some(1000));
+        estimation.update_at(&mut second, start + Duration::from_secs(2));
+        // 100 * 0.7 + 300 * 0.3
+        let throughput = second.throughput.expect("throughput is estimated");
+        assert!((throughput - 160.0).abs() < 1e-9);
+        assert_eq!(second.eta, Some(3750));
+    }
+
+    #[test]
+    fn no_eta_without_total() {
+        let start = Instant::now();
+        let mut estimation = Estimation::since(start, 0);
+        let mut ticks = ticks(10, None);
+        estimation.update_at(&mut ticks, start + Duration::from_secs(1));
+        assert_eq!(ticks.throughput, Some(10.0));
+        assert_eq!(ticks.eta, None);
+    }
+
+    #[test]
+    fn done_when_count_reaches_or_exceeds_total() {
+        let start = Instant::now();
+        let mut estimation = Estimation::since(start, 0);
+        let mut exact = ticks(10, Some(10));
+        estimation.update_at(&mut exact, start + Duration::from_secs(1));
+        assert!(exact.done());
+        assert_eq!(exact.eta, Some(0));
+        let mut over = ticks(12, Some(10));
+        estimation.update_at(&mut over, start + Duration::from_secs(2));
+        assert!(over.done());
+        assert_eq!(over.eta, Some(0));
+    }
+
+    #[test]
+    fn reset_counter() {
+        let start = Instant::now();
+        let mut estimation = Estimation::since(start, 0);
+        estimation.update_at(&mut ticks(50, Some(100)), start + Duration::from_secs(1));
+        let mut reset = ticks(5, Some(100));
+        estimation.update_at(&mut reset, start + Duration::from_secs(2));
+        assert_eq!(reset.throughput, None);
+        assert_eq!(reset.eta, None);
+    }
+
+    #[test]
+    fn seeded_from_first_ticks() {
+        // Ticks without preceding `Started`: the first update gives no estimation
+        // instead of treating all the ticks as done since the tracker has started
+        let start = Instant::now();
+        let mut estimation = Estimation::since(start, 500);
+        let mut first = ticks(500, Some(1000));
+        estimation.update_at(&mut first, start + Duration::from_millis(10));
+        assert_eq!(first.throughput, None);
+        assert_eq!(first.eta, None);
+        let mut second = ticks(600, Some(1000));
+        estimation.update_at(&mut second, start + Duration::from_secs(1));
+        assert_eq!(second.throughput, Some(100.0));
+        assert_eq!(second.eta, Some(4000));
+    }
+}
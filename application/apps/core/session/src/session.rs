@@ -1,5 +1,6 @@
 use crate::{
     events::{CallbackEvent, ComputationError, Severity},
+    faults::Faults,
     operations,
     operations::Operation,
     state,
@@ -38,6 +39,22 @@ impl Session {
     ///
     pub async fn new(
         uuid: Uuid,
+    ) -> Result<(Self, UnboundedReceiver<CallbackEvent>), ComputationError> {
+        Self::start(uuid, Faults::default()).await
+    }
+
+    /// Same as `Session::new`, but with faults injected into the loops of session
+    #[cfg(feature = "fault-injection")]
+    pub async fn with_faults(
+        uuid: Uuid,
+        faults: Faults,
+    ) -> Result<(Self, UnboundedReceiver<CallbackEvent>), ComputationError> {
+        Self::start(uuid, faults).await
+    }
+
+    async fn start(
+        uuid: Uuid,
+        faults: Faults,
     ) -> Result<(Self, UnboundedReceiver<CallbackEvent>), ComputationError> {
         let (tx_operations, rx_operations): OperationsChannel = unbounded_channel();
         let (tracker_api, rx_tracker_api) = OperationTrackerAPI::new();
@@ -64,6 +81,8 @@ impl Session {
                         state_api.clone(),
                         tracker_api.clone(),
                         tx_callback_events.clone(),
+                        uuid,
+                        faults.clone(),
                     )
                     .await;
                     if let Err(err) = state_api.shutdown() {
@@ -71,7 +90,9 @@ impl Session {
                     }
                 },
                 async {
-                    if let Err(err) = state::run(rx_state_api, tx_callback_events_state).await {
+                    if let Err(err) =
+                        state::run(rx_state_api, tx_callback_events_state, faults.clone()).await
+                    {
                         error!("State loop exits with error:: {:?}", err);
                         if let Err(err) =
                             Session::send_stop_signal(Uuid::new_v4(), &tx_operations, None).await
@@ -81,7 +102,9 @@ impl Session {
                     }
                 },
                 async {
-                    if let Err(err) = tracker::run(state_api.clone(), rx_tracker_api).await {
+                    if let Err(err) =
+                        tracker::run(state_api.clone(), rx_tracker_api, faults.clone()).await
+                    {
                         error!("Tracker loop exits with error:: {:?}", err);
                         if let Err(err) =
                             Session::send_stop_signal(Uuid::new_v4(), &tx_operations, None).await
@@ -104,13 +127,27 @@ impl Session {
         self.state.clone()
     }
 
-    pub fn abort(&self, operation_id: Uuid, target: Uuid) -> Result<(), ComputationError> {
+    /// Sends operation into operations loop. If `operation_id` isn't given, a new
+    /// uuid is allocated. Returns uuid of operation, which will be used in
+    /// the events of operation.
+    fn send_operation(
+        &self,
+        operation_id: Option<Uuid>,
+        kind: operations::OperationKind,
+    ) -> Result<Uuid, ComputationError> {
+        let operation_id = operation_id.unwrap_or_else(Uuid::new_v4);
         self.tx_operations
-            .send(Operation::new(
-                operation_id,
-                operations::OperationKind::Cancel { target },
-            ))
-            .map_err(|e| ComputationError::Communication(e.to_string()))
+            .send(Operation::new(operation_id, kind))
+            .map_err(|e| ComputationError::Communication(e.to_string()))?;
+        Ok(operation_id)
+    }
+
+    pub fn abort(
+        &self,
+        operation_id: Option<Uuid>,
+        target: Uuid,
+    ) -> Result<Uuid, ComputationError> {
+        self.send_operation(operation_id, operations::OperationKind::Cancel { target })
     }
 
     pub(crate) async fn send_stop_signal(
@@ -127,44 +164,44 @@ impl Session {
         Ok(())
     }
 
-    pub async fn stop(&self, operation_id: Uuid) -> Result<(), ComputationError> {
-        Session::send_stop_signal(operation_id, &self.tx_operations, Some(&self.destroyed)).await
+    pub async fn stop(&self, operation_id: Option<Uuid>) -> Result<(), ComputationError> {
+        Session::send_stop_signal(
+            operation_id.unwrap_or_else(Uuid::new_v4),
+            &self.tx_operations,
+            Some(&self.destroyed),
+        )
+        .await
     }
 
     pub fn external_call_lib(
         &self,
-        operation_id: Uuid,
+        operation_id: Option<Uuid>,
         path: String,
         a: u64,
         b: u64,
         lines: Vec<String>,
-    ) -> Result<(), ComputationError> {
-        self.tx_operations
-            .send(Operation::new(
-                operation_id,
-                operations::OperationKind::ExternalLibCall(path, a, b, lines),
-            ))
-            .map_err(|e| ComputationError::Communication(e.to_string()))
+    ) -> Result<Uuid, ComputationError> {
+        self.send_operation(
+            operation_id,
+            operations::OperationKind::ExternalLibCall(path, a, b, lines),
+        )
     }
 
     /// Used for debug goals
-    pub fn sleep(&self, operation_id: Uuid, ms: u64) -> Result<(), ComputationError> {
-        self.tx_operations
-            .send(Operation::new(
-                operation_id,
-                operations::OperationKind::Sleep(ms),
-            ))
-            .map_err(|e| ComputationError::Communication(e.to_string()))
+    pub fn sleep(&self, operation_id: Option<Uuid>, ms: u64) -> Result<Uuid, ComputationError> {
+        self.send_operation(operation_id, operations::OperationKind::Sleep(ms))
     }
 
-    /// Used for debug goals
+    /// Used for debug goals; stops the state loop with an error
+    #[cfg(feature = "fault-injection")]
     pub async fn trigger_state_error(&self) -> Result<(), ComputationError> {
         self.state
             .shutdown_with_error()
             .map_err(ComputationError::NativeError)
     }
 
-    /// Used for debug goals
+    /// Used for debug goals; stops the tracker loop with an error
+    #[cfg(feature = "fault-injection")]
     pub async fn trigger_tracker_error(&self) -> Result<(), ComputationError> {
         self.tracker
             .shutdown_with_error()
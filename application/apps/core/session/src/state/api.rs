@@ -13,6 +13,7 @@ pub enum Api {
     NotifyCancelingOperation(Uuid),
     NotifyCanceledOperation(Uuid),
     // Used for tests of error handeling
+    #[cfg(feature = "fault-injection")]
     ShutdownWithError,
     Shutdown,
 }
@@ -28,6 +29,7 @@ impl Display for Api {
                 Self::NotifyCancelingOperation(_) => "NotifyCancelingOperation",
                 Self::NotifyCanceledOperation(_) => "NotifyCanceledOperation",
                 Self::Shutdown => "Shutdown",
+                #[cfg(feature = "fault-injection")]
                 Self::ShutdownWithError => "ShutdownWithError",
             }
         )
@@ -108,6 +110,7 @@ impl SessionStateAPI {
         })
     }
 
+    #[cfg(feature = "fault-injection")]
     pub fn shutdown_with_error(&self) -> Result<(), NativeError> {
         self.tx_api.send(Api::ShutdownWithError).map_err(|e| {
             NativeError::channel(&format!(
@@ -1,4 +1,7 @@
-use crate::events::{CallbackEvent, NativeError, NativeErrorKind, Severity};
+use crate::{
+    events::{CallbackEvent, NativeError},
+    faults::{Faults, Injected, Loop},
+};
 use log::debug;
 use std::collections::HashMap;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
@@ -35,11 +38,17 @@ impl SessionState {
 pub async fn run(
     mut rx_api: UnboundedReceiver<Api>,
     tx_callback_events: UnboundedSender<CallbackEvent>,
+    faults: Faults,
 ) -> Result<(), NativeError> {
     let mut state = SessionState::new(tx_callback_events.clone());
     let state_cancellation_token = CancellationToken::new();
     debug!("task is started");
     while let Some(msg) = rx_api.recv().await {
+        match faults.hit(Loop::State).await {
+            Some(Injected::Drop) => break,
+            Some(Injected::Fail(err)) => return Err(err),
+            None => {}
+        }
         match msg {
             Api::CloseSession(tx_response) => {
                 state_cancellation_token.cancel();
@@ -71,13 +80,14 @@ pub async fn run(
                 debug!("shutdown has been requested");
                 break;
             }
+            #[cfg(feature = "fault-injection")]
             Api::ShutdownWithError => {
                 debug!("shutdown state loop with error for testing");
-                return Err(NativeError {
-                    severity: Severity::ERROR,
-                    kind: NativeErrorKind::Io,
-                    message: Some(String::from("Shutdown state loop with error for testing")),
-                });
+                return Err(NativeError::new(
+                    crate::events::Severity::ERROR,
+                    crate::events::NativeErrorKind::Io,
+                    "Shutdown state loop with error for testing",
+                ));
             }
         }
     }
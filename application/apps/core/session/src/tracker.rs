@@ -1,5 +1,6 @@
 use crate::{
     events::{NativeError, NativeErrorKind, Severity},
+    faults::{Faults, Injected, Loop},
     operations::OperationStat,
     progress::ProgressProviderAPI,
     state::SessionStateAPI,
@@ -30,6 +31,7 @@ pub enum TrackerCommand {
     CancelAll(oneshot::Sender<()>),
     Shutdown,
     // Used for tests of error handeling
+    #[cfg(feature = "fault-injection")]
     ShutdownWithError,
 }
 
@@ -46,6 +48,7 @@ impl std::fmt::Display for TrackerCommand {
                 Self::GetOperationsStat(_) => "GetOperationsStat",
                 Self::CancelAll(_) => "CancelAll",
                 Self::Shutdown => "Shutdown",
+                #[cfg(feature = "fault-injection")]
                 Self::ShutdownWithError => "ShutdownWithError",
             }
         )
@@ -135,6 +138,7 @@ impl OperationTrackerAPI {
         })
     }
 
+    #[cfg(feature = "fault-injection")]
     pub fn shutdown_with_error(&self) -> Result<(), NativeError> {
         self.tx_api
             .send(TrackerCommand::ShutdownWithError)
@@ -149,6 +153,7 @@ impl OperationTrackerAPI {
 pub async fn run(
     state: SessionStateAPI,
     mut rx_api: UnboundedReceiver<TrackerCommand>,
+    faults: Faults,
 ) -> Result<(), NativeError> {
     let mut tracker = OperationTracker {
         operations: HashMap::new(),
@@ -158,6 +163,11 @@ pub async fn run(
     let progress = ProgressProviderAPI::new()?;
     debug!("task is started");
     while let Some(msg) = rx_api.recv().await {
+        match faults.hit(Loop::Tracker).await {
+            Some(Injected::Drop) => break,
+            Some(Injected::Fail(err)) => return Err(err),
+            None => {}
+        }
         match msg {
             TrackerCommand::AddOperation((
                 uuid,
@@ -275,11 +285,11 @@ pub async fn run(
                 if tx_response
                     .send(match serde_json::to_string(&tracker.stat) {
                         Ok(serialized) => Ok(serialized),
-                        Err(err) => Err(NativeError {
-                            severity: Severity::ERROR,
-                            kind: NativeErrorKind::ComputationFailed,
-                            message: Some(format!("{err}")),
-                        }),
+                        Err(err) => Err(NativeError::new(
+                            Severity::ERROR,
+                            NativeErrorKind::ComputationFailed,
+                            format!("{err}"),
+                        )),
                     })
                     .is_err()
                 {
@@ -292,13 +302,14 @@ pub async fn run(
                 debug!("shutdown has been requested");
                 break;
             }
+            #[cfg(feature = "fault-injection")]
             TrackerCommand::ShutdownWithError => {
                 debug!("shutdown tracker loop with error for testing");
-                return Err(NativeError {
-                    severity: Severity::ERROR,
-                    kind: NativeErrorKind::Io,
-                    message: Some(String::from("Shutdown tracker loop with error for testing")),
-                });
+                return Err(NativeError::new(
+                    Severity::ERROR,
+                    NativeErrorKind::Io,
+                    "Shutdown tracker loop with error for testing",
+                ));
             }
         }
     }
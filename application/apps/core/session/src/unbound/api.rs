@@ -1,8 +1,19 @@
 use crate::events::ComputationError;
-use serde::Serialize;
-use tokio::sync::{mpsc::UnboundedSender, oneshot};
+use futures::future::{BoxFuture, FutureExt};
+use log::error;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use tokio::{
+    select,
+    sync::{mpsc::UnboundedSender, oneshot},
+};
+use uuid::Uuid;
 
-use super::commands::{Command, CommandOutcome};
+use super::{
+    cache::{CacheStats, CachedValue},
+    commands::{dispatch, Command, CommandOutcome, OnDuplicate, UnboundCommand, Visitor},
+};
+use std::time::Duration;
 
 #[derive(Debug)]
 pub enum API {
@@ -10,7 +21,11 @@ pub enum API {
     CancelJob(u64),
     Run(Command, u64),
     /// remove finished jobs from registry
-    Remove(u64),
+    Remove(Uuid),
+    /// store results of pure job in cache
+    Cache(&'static str, String, CachedValue, Option<Duration>),
+    CacheStats(oneshot::Sender<CacheStats>),
+    ClearCache,
 }
 
 #[derive(Clone, Debug)]
@@ -41,39 +56,244 @@ impl UnboundSessionAPI {
         })
     }
 
-    async fn process_command<T: Serialize>(
+    /// Runs given job in the scope of unbound session and waits for results.
+    /// Job is rejected if a job with the same id already exists
+    pub async fn run<C: UnboundCommand>(
         &self,
         id: u64,
-        rx_results: oneshot::Receiver<Result<CommandOutcome<T>, ComputationError>>,
-        command: Command,
-    ) -> Result<CommandOutcome<T>, ComputationError> {
-        self.tx.send(API::Run(command, id)).map_err(|_| {
-            ComputationError::Communication(String::from("Fail to send call Job::SomeJob"))
-        })?;
+        command: C,
+    ) -> Result<CommandOutcome<C::Output>, ComputationError> {
+        self.run_with_policy(id, command, OnDuplicate::Reject).await
+    }
+
+    /// Runs given job in the scope of unbound session and waits for results.
+    /// If a job with the same id already exists, it will be cancelled. Useful
+    /// to debounce repeated requests.
+    pub async fn run_replacing<C: UnboundCommand>(
+        &self,
+        id: u64,
+        command: C,
+    ) -> Result<CommandOutcome<C::Output>, ComputationError> {
+        self.run_with_policy(id, command, OnDuplicate::Replace)
+            .await
+    }
+
+    /// Runs given job with the given behavior in case a job with the same
+    /// id already exists (see `OnDuplicate`)
+    pub async fn run_with_policy<C: UnboundCommand>(
+        &self,
+        id: u64,
+        command: C,
+        on_duplicate: OnDuplicate,
+    ) -> Result<CommandOutcome<C::Output>, ComputationError> {
+        let cache_key = command.cache_key().await;
+        let (command, rx_results) = Command::new(command);
+        self.tx
+            .send(API::Run(
+                command.on_duplicate(on_duplicate).with_cache_key(cache_key),
+                id,
+            ))
+            .map_err(|_| {
+                ComputationError::Communication(format!("Fail to send call Job::{}", C::ALIAS))
+            })?;
         rx_results
             .await
             .map_err(|e| ComputationError::Communication(format!("channel error: {e}")))?
     }
 
-    pub(crate) fn remove_command(&self, id: u64) -> Result<(), ComputationError> {
-        self.tx.send(API::Remove(id)).map_err(|_| {
-            ComputationError::Communication(String::from("Fail to send call Job::SomeJob"))
-        })?;
-        Ok(())
+    /// Runs given job and delivers its intermediate results into `callback`
+    /// until the job is done. `on_duplicate` works the same way as for
+    /// `run_with_policy`
+    pub async fn run_with_partials<C, F>(
+        &self,
+        id: u64,
+        command: C,
+        on_duplicate: OnDuplicate,
+        callback: F,
+    ) -> Result<CommandOutcome<C::Output>, ComputationError>
+    where
+        C: UnboundCommand,
+        F: Fn(CommandOutcome<C::Output>) + Send,
+    {
+        let cache_key = command.cache_key().await;
+        let (command, mut rx_results, mut rx_partials) = Command::with_partials(command);
+        self.tx
+            .send(API::Run(
+                command.on_duplicate(on_duplicate).with_cache_key(cache_key),
+                id,
+            ))
+            .map_err(|_| {
+                ComputationError::Communication(format!("Fail to send call Job::{}", C::ALIAS))
+            })?;
+        let mut partials_closed = false;
+        loop {
+            select! {
+                partial = rx_partials.recv(), if !partials_closed => {
+                    if let Some(partial) = partial {
+                        callback(partial);
+                    } else {
+                        partials_closed = true;
+                    }
+                }
+                results = &mut rx_results => {
+                    // Deliver partials, which are still in the channel
+                    while let Ok(partial) = rx_partials.try_recv() {
+                        callback(partial);
+                    }
+                    return results.map_err(|e| {
+                        ComputationError::Communication(format!("channel error: {e}"))
+                    })?;
+                }
+            }
+        }
+    }
+
+    /// Runs job with given alias (see `UnboundCommand::ALIAS`). Arguments of
+    /// job are deserialized from `args` and results are converted into JSON
+    /// value, so the caller doesn't need to know the type of job.
+    pub async fn run_by_alias(
+        &self,
+        id: u64,
+        alias: &str,
+        args: Value,
+        on_duplicate: OnDuplicate,
+    ) -> Result<CommandOutcome<Value>, ComputationError> {
+        self.dispatch_run(id, alias, args, on_duplicate, None).await
+    }
+
+    /// Same as `run_by_alias`, but delivers intermediate results of job into
+    /// `callback` (see `run_with_partials`)
+    pub async fn run_by_alias_with_partials<F>(
+        &self,
+        id: u64,
+        alias: &str,
+        args: Value,
+        on_duplicate: OnDuplicate,
+        callback: F,
+    ) -> Result<CommandOutcome<Value>, ComputationError>
+    where
+        F: Fn(CommandOutcome<Value>) + Send + 'static,
+    {
+        self.dispatch_run(id, alias, args, on_duplicate, Some(Box::new(callback)))
+            .await
     }
 
-    pub async fn cancel_test(
+    async fn dispatch_run(
         &self,
         id: u64,
-        custom_arg_a: i64,
-        custom_arg_b: i64,
-    ) -> Result<CommandOutcome<i64>, ComputationError> {
-        let (tx_results, rx_results) = oneshot::channel();
-        self.process_command(
+        alias: &str,
+        args: Value,
+        on_duplicate: OnDuplicate,
+        partials: Option<PartialCallback>,
+    ) -> Result<CommandOutcome<Value>, ComputationError> {
+        let run = RunByAlias {
+            api: self,
             id,
-            rx_results,
-            Command::CancelTest(custom_arg_a, custom_arg_b, tx_results),
-        )
-        .await
+            args,
+            on_duplicate,
+            partials,
+        };
+        match dispatch(alias, run) {
+            Some(running) => running.await,
+            None => Err(ComputationError::InvalidArgs(format!(
+                "Job \"{alias}\" isn't found"
+            ))),
+        }
     }
+
+    pub(crate) fn cache_results(
+        &self,
+        alias: &'static str,
+        key: String,
+        value: CachedValue,
+        ttl: Option<Duration>,
+    ) -> Result<(), ComputationError> {
+        self.tx
+            .send(API::Cache(alias, key, value, ttl))
+            .map_err(|_| ComputationError::Communication(String::from("Fail to send API::Cache")))
+    }
+
+    pub async fn cache_stats(&self) -> Result<CacheStats, ComputationError> {
+        let (tx, rx) = oneshot::channel();
+        self.tx.send(API::CacheStats(tx)).map_err(|_| {
+            ComputationError::Communication(String::from("Fail to send API::CacheStats"))
+        })?;
+        rx.await.map_err(|e| {
+            ComputationError::Communication(format!(
+                "Fail to get response from API::CacheStats: {e:?}"
+            ))
+        })
+    }
+
+    pub fn clear_cache(&self) -> Result<(), ComputationError> {
+        self.tx.send(API::ClearCache).map_err(|_| {
+            ComputationError::Communication(String::from("Fail to send API::ClearCache"))
+        })
+    }
+
+    pub(crate) fn remove_command(&self, uuid: Uuid) -> Result<(), ComputationError> {
+        self.tx.send(API::Remove(uuid)).map_err(|_| {
+            ComputationError::Communication(String::from("Fail to send call Job::SomeJob"))
+        })?;
+        Ok(())
+    }
+}
+
+type PartialCallback = Box<dyn Fn(CommandOutcome<Value>) + Send>;
+
+/// Runs job of the type found by alias
+struct RunByAlias<'a> {
+    api: &'a UnboundSessionAPI,
+    id: u64,
+    args: Value,
+    on_duplicate: OnDuplicate,
+    partials: Option<PartialCallback>,
+}
+
+impl<'a> Visitor for RunByAlias<'a> {
+    type Output = BoxFuture<'a, Result<CommandOutcome<Value>, ComputationError>>;
+
+    fn visit<C: UnboundCommand + DeserializeOwned>(self) -> Self::Output {
+        async move {
+            let command: C = serde_json::from_value(self.args).map_err(|e| {
+                ComputationError::InvalidArgs(format!("Arguments of job {}: {e}", C::ALIAS))
+            })?;
+            let outcome = match self.partials {
+                Some(callback) => {
+                    self.api
+                        .run_with_partials(self.id, command, self.on_duplicate, move |partial| {
+                            match as_value(partial) {
+                                Ok(partial) => callback(partial),
+                                Err(err) => error!(
+                                    "Fail to convert intermediate results of {}: {err}",
+                                    C::ALIAS
+                                ),
+                            }
+                        })
+                        .await?
+                }
+                None => {
+                    self.api
+                        .run_with_policy(self.id, command, self.on_duplicate)
+                        .await?
+                }
+            };
+            as_value(outcome)
+        }
+        .boxed()
+    }
+}
+
+fn as_value<T: Serialize>(
+    outcome: CommandOutcome<T>,
+) -> Result<CommandOutcome<Value>, ComputationError> {
+    let value = |output: T| {
+        serde_json::to_value(output)
+            .map_err(|e| ComputationError::Process(format!("Fail to convert results of job: {e}")))
+    };
+    Ok(match outcome {
+        CommandOutcome::Finished(output) => CommandOutcome::Finished(value(output)?),
+        CommandOutcome::Partial(output) => CommandOutcome::Partial(value(output)?),
+        CommandOutcome::Cancelled => CommandOutcome::Cancelled,
+    })
 }
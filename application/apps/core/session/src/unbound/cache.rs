@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    any::Any,
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// Default max number of results kept in cache
+pub const DEFAULT_CACHE_CAPACITY: usize = 256;
+
+pub type CachedValue = Box<dyn Any + Send>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+struct Entry {
+    value: CachedValue,
+    stored: Instant,
+    ttl: Option<Duration>,
+    last_used: u64,
+}
+
+impl Entry {
+    fn is_expired(&self) -> bool {
+        self.ttl.is_some_and(|ttl| self.stored.elapsed() > ttl)
+    }
+}
+
+/// LRU cache of results of unbound jobs. Results are identified by alias of
+/// job and the key provided by the job (see `UnboundCommand::cache_key`)
+pub struct ResultsCache {
+    entries: HashMap<(&'static str, String), Entry>,
+    capacity: usize,
+    usage: u64,
+    stats: CacheStats,
+}
+
+impl ResultsCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            capacity,
+            usage: 0,
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// Returns cached value if it exists and isn't expired. Counts hits and misses
+    pub fn get(&mut self, alias: &'static str, key: &str) -> Option<&CachedValue> {
+        let id = (alias, key.to_owned());
+        if self
+            .entries
+            .get(&id)
+            .is_some_and(|entry| entry.is_expired())
+        {
+            self.entries.remove(&id);
+        }
+        self.usage += 1;
+        match self.entries.get_mut(&id) {
+            Some(entry) => {
+                self.stats.hits += 1;
+                entry.last_used = self.usage;
+                Some(&entry.value)
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub fn insert(
+        &mut self,
+        alias: &'static str,
+        key: String,
+        value: CachedValue,
+        ttl: Option<Duration>,
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+        let id = (alias, key);
+        if !self.entries.contains_key(&id) && self.entries.len() >= self.capacity {
+            let lru = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(id, _)| id.clone());
+            if let Some(lru) = lru {
+                self.entries.remove(&lru);
+            }
+        }
+        self.usage += 1;
+        self.entries.insert(
+            id,
+            Entry {
+                value,
+                stored: Instant::now(),
+                ttl,
+                last_used: self.usage,
+            },
+        );
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            entries: self.entries.len(),
+            ..self.stats.clone()
+        }
+    }
+}
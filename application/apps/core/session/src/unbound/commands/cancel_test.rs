@@ -1,23 +1,35 @@
 use crate::{
     events::ComputationError,
-    unbound::{commands::CommandOutcome, signal::Signal},
+    unbound::{
+        commands::{CommandOutcome, UnboundCommand},
+        signal::Signal,
+    },
 };
+use serde::Deserialize;
 use tokio::{
     select,
     time::{sleep, Duration},
 };
 
-pub async fn cancel_test(
-    custom_arg_a: i64,
-    custom_arg_b: i64,
-    signal: Signal,
-) -> Result<CommandOutcome<i64>, ComputationError> {
-    Ok(select! {
-        _ = signal.cancelled() => {
-            CommandOutcome::Cancelled
-        }
-        _ = sleep(Duration::from_millis(500)) => {
-            CommandOutcome::Finished(custom_arg_a + custom_arg_b)
-        }
-    })
+/// This command is used only for testing/debug goals
+#[derive(Debug, Deserialize)]
+pub struct CancelTest {
+    pub custom_arg_a: i64,
+    pub custom_arg_b: i64,
+}
+
+impl UnboundCommand for CancelTest {
+    type Output = i64;
+    const ALIAS: &'static str = "cancelTest";
+
+    async fn run(self, signal: Signal) -> Result<CommandOutcome<i64>, ComputationError> {
+        Ok(select! {
+            _ = signal.cancelled() => {
+                CommandOutcome::Cancelled
+            }
+            _ = sleep(Duration::from_millis(500)) => {
+                CommandOutcome::Finished(self.custom_arg_a + self.custom_arg_b)
+            }
+        })
+    }
 }
@@ -0,0 +1,59 @@
+use crate::{
+    events::ComputationError,
+    unbound::{
+        commands::{file_cache_key, CommandOutcome, CommandResult, UnboundCommand},
+        signal::Signal,
+    },
+};
+use serde::Deserialize;
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+};
+use tokio::task;
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, Deserialize)]
+pub struct Checksum {
+    pub filename: String,
+}
+
+impl UnboundCommand for Checksum {
+    type Output = String;
+    const ALIAS: &'static str = "checksum";
+    const CONCURRENCY: Option<usize> = Some(2);
+
+    async fn cache_key(&self) -> Option<String> {
+        file_cache_key(&self.filename).await
+    }
+
+    async fn run(self, signal: Signal) -> CommandResult<String> {
+        task::spawn_blocking(move || checksum(self.filename, signal))
+            .await
+            .map_err(|e| ComputationError::Process(format!("Fail to calculate checksum: {e}")))?
+    }
+}
+
+fn checksum(filename: String, signal: Signal) -> CommandResult<String> {
+    let file = File::open(&filename)
+        .map_err(|e| ComputationError::IoOperation(format!("{filename}: {e}")))?;
+    let mut reader = BufReader::new(file);
+    let mut hasher = blake3::Hasher::new();
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+    loop {
+        if signal.is_cancelling() {
+            return Ok(CommandOutcome::Cancelled);
+        }
+        let read = reader
+            .read(&mut buffer)
+            .map_err(|e| ComputationError::IoOperation(format!("{filename}: {e}")))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(CommandOutcome::Finished(
+        hasher.finalize().to_hex().to_string(),
+    ))
+}
@@ -0,0 +1,116 @@
+use crate::{
+    events::ComputationError,
+    unbound::{
+        commands::{file_cache_key, CommandOutcome, CommandResult, UnboundCommand},
+        signal::Signal,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::{fs::File, io::Read};
+use tokio::task;
+
+/// Amount of bytes from the beginning of the file used for detection
+const SAMPLE_SIZE: u64 = 64 * 1024;
+/// Share of control characters starting from which text is considered as binary
+const BINARY_THRESHOLD: f64 = 0.1;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum FileEncoding {
+    Ascii,
+    Utf8,
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum FileType {
+    Text,
+    Binary,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FileFormat {
+    pub encoding: FileEncoding,
+    pub file_type: FileType,
+}
+
+fn detect(sample: &[u8]) -> FileFormat {
+    let with_bom = |encoding: FileEncoding| FileFormat {
+        encoding,
+        file_type: FileType::Text,
+    };
+    if sample.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        return with_bom(FileEncoding::Utf8Bom);
+    }
+    if sample.starts_with(&[0xFF, 0xFE]) {
+        return with_bom(FileEncoding::Utf16Le);
+    }
+    if sample.starts_with(&[0xFE, 0xFF]) {
+        return with_bom(FileEncoding::Utf16Be);
+    }
+    let controls = sample
+        .iter()
+        .filter(|b| **b == 0 || (**b < 0x20 && !matches!(**b, b'\t' | b'\n' | b'\r' | 0x0C)))
+        .count();
+    let binary = sample.contains(&0)
+        || (!sample.is_empty() && controls as f64 / sample.len() as f64 > BINARY_THRESHOLD);
+    let encoding = if sample.is_ascii() {
+        FileEncoding::Ascii
+    } else {
+        match std::str::from_utf8(sample) {
+            Ok(_) => FileEncoding::Utf8,
+            // Sample can be cut in the middle of a multi-byte char
+            Err(e) if e.error_len().is_none() => FileEncoding::Utf8,
+            Err(_) => FileEncoding::Unknown,
+        }
+    };
+    FileFormat {
+        encoding: if binary {
+            FileEncoding::Unknown
+        } else {
+            encoding
+        },
+        file_type: if binary {
+            FileType::Binary
+        } else {
+            FileType::Text
+        },
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetFileFormat {
+    pub filename: String,
+}
+
+impl UnboundCommand for GetFileFormat {
+    type Output = FileFormat;
+    const ALIAS: &'static str = "getFileFormat";
+    const CONCURRENCY: Option<usize> = Some(4);
+
+    async fn cache_key(&self) -> Option<String> {
+        file_cache_key(&self.filename).await
+    }
+
+    async fn run(self, signal: Signal) -> CommandResult<FileFormat> {
+        task::spawn_blocking(move || get_file_format(self.filename, signal))
+            .await
+            .map_err(|e| ComputationError::Process(format!("Fail to detect file format: {e}")))?
+    }
+}
+
+fn get_file_format(filename: String, signal: Signal) -> CommandResult<FileFormat> {
+    let file = File::open(&filename)
+        .map_err(|e| ComputationError::IoOperation(format!("{filename}: {e}")))?;
+    let mut sample = Vec::new();
+    file.take(SAMPLE_SIZE)
+        .read_to_end(&mut sample)
+        .map_err(|e| ComputationError::IoOperation(format!("{filename}: {e}")))?;
+    Ok(if signal.is_cancelling() {
+        CommandOutcome::Cancelled
+    } else {
+        CommandOutcome::Finished(detect(&sample))
+    })
+}
@@ -0,0 +1,209 @@
+use crate::{
+    events::ComputationError,
+    unbound::{
+        commands::{CommandOutcome, CommandResult, UnboundCommand},
+        signal::Signal,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+use tokio::task;
+
+/// Amount of entities in one batch of intermediate results
+const PARTIAL_BATCH_SIZE: usize = 500;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum EntityType {
+    File,
+    Directory,
+    SymbolicLink,
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EntityDetails {
+    pub filename: String,
+    pub full_filename: String,
+    pub path: String,
+    pub basename: String,
+    pub ext: String,
+    pub size: u64,
+    /// Time of last modification in ms since UNIX epoch
+    pub modified: Option<u64>,
+    pub readonly: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Entity {
+    pub name: String,
+    pub fullname: String,
+    pub kind: EntityType,
+    pub details: Option<EntityDetails>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct FolderContent {
+    pub list: Vec<Entity>,
+    /// true if the listing has been stopped because of `max_len`
+    pub max_len_reached: bool,
+}
+
+impl Entity {
+    fn from_path(path: &Path) -> Option<Self> {
+        let metadata = fs::symlink_metadata(path).ok()?;
+        let file_type = metadata.file_type();
+        let kind = if file_type.is_symlink() {
+            EntityType::SymbolicLink
+        } else if file_type.is_dir() {
+            EntityType::Directory
+        } else if file_type.is_file() {
+            EntityType::File
+        } else {
+            EntityType::Other
+        };
+        let name = path.file_name()?.to_string_lossy().to_string();
+        let details = if kind == EntityType::Directory {
+            None
+        } else {
+            Some(EntityDetails {
+                filename: name.clone(),
+                full_filename: path.to_string_lossy().to_string(),
+                path: path
+                    .parent()
+                    .map(|p| p.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                basename: path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                ext: path
+                    .extension()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                size: metadata.len(),
+                modified: metadata
+                    .modified()
+                    .ok()
+                    .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                    .map(|d| d.as_millis() as u64),
+                readonly: metadata.permissions().readonly(),
+            })
+        };
+        Some(Entity {
+            name,
+            fullname: path.to_string_lossy().to_string(),
+            kind,
+            details,
+        })
+    }
+}
+
+struct Walker<'a> {
+    depth: usize,
+    max_len: usize,
+    include_files: bool,
+    include_folders: bool,
+    signal: &'a Signal,
+    content: FolderContent,
+}
+
+impl<'a> Walker<'a> {
+    /// Reports progress and sends the last batch of entities as intermediate
+    /// results, if the batch is full
+    fn report(&self) {
+        let len = self.content.list.len();
+        if !len.is_multiple_of(PARTIAL_BATCH_SIZE) {
+            return;
+        }
+        self.signal.progress(len as u64, None, None);
+        if self.signal.has_partials() {
+            self.signal.partial(FolderContent {
+                list: self.content.list[len - PARTIAL_BATCH_SIZE..].to_vec(),
+                max_len_reached: false,
+            });
+        }
+    }
+
+    /// Returns false if walking should be stopped
+    fn walk(&mut self, path: &Path, level: usize) -> bool {
+        let Ok(entries) = fs::read_dir(path) else {
+            return true;
+        };
+        let mut entries: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+        entries.sort();
+        for entry_path in entries {
+            if self.signal.is_cancelling() {
+                return false;
+            }
+            let Some(entity) = Entity::from_path(&entry_path) else {
+                continue;
+            };
+            let is_dir = entity.kind == EntityType::Directory;
+            if (is_dir && self.include_folders) || (!is_dir && self.include_files) {
+                if self.content.list.len() >= self.max_len {
+                    self.content.max_len_reached = true;
+                    return false;
+                }
+                self.content.list.push(entity);
+                self.report();
+            }
+            if is_dir && level + 1 < self.depth && !self.walk(&entry_path, level + 1) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetFolderContent {
+    pub paths: Vec<String>,
+    pub depth: usize,
+    pub max_len: usize,
+    pub include_files: bool,
+    pub include_folders: bool,
+}
+
+impl UnboundCommand for GetFolderContent {
+    type Output = FolderContent;
+    const ALIAS: &'static str = "getFolderContent";
+    const CONCURRENCY: Option<usize> = Some(2);
+
+    async fn run(self, signal: Signal) -> CommandResult<FolderContent> {
+        task::spawn_blocking(move || get_folder_content(self, signal))
+            .await
+            .map_err(|e| ComputationError::Process(format!("Fail to read folder content: {e}")))?
+    }
+}
+
+fn get_folder_content(command: GetFolderContent, signal: Signal) -> CommandResult<FolderContent> {
+    let mut walker = Walker {
+        depth: command.depth,
+        max_len: command.max_len,
+        include_files: command.include_files,
+        include_folders: command.include_folders,
+        signal: &signal,
+        content: FolderContent::default(),
+    };
+    for path in command.paths.iter() {
+        let path = PathBuf::from(path);
+        if !path.is_dir() {
+            return Err(ComputationError::InvalidArgs(format!(
+                "{} isn't a folder",
+                path.to_string_lossy()
+            )));
+        }
+        if !walker.walk(&path, 0) {
+            break;
+        }
+    }
+    Ok(if signal.is_cancelling() {
+        CommandOutcome::Cancelled
+    } else {
+        CommandOutcome::Finished(walker.content)
+    })
+}
@@ -1,22 +1,39 @@
-mod cancel_test;
+pub mod cancel_test;
+pub mod checksum;
+pub mod file_format;
+pub mod folder_content;
+pub mod regex;
+pub mod serial_ports;
 
 use crate::events::ComputationError;
+use futures::future::{BoxFuture, FutureExt};
 use log::{error, trace};
-use serde::{Deserialize, Serialize};
-use tokio::sync::oneshot;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{future::Future, time::Duration, time::UNIX_EPOCH};
+use tokio::sync::{
+    mpsc::{unbounded_channel, UnboundedReceiver},
+    oneshot,
+};
 use uuid::Uuid;
 
-use super::signal::Signal;
+use super::{
+    cache::CachedValue,
+    signal::{PartialSink, Signal},
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum CommandOutcome<T> {
     Finished(T),
+    /// Intermediate results of job. Never used as final results; delivered
+    /// only to listeners of intermediate results (see `Signal::partial`)
+    Partial(T),
     Cancelled,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum UuidCommandOutcome<T: Serialize> {
     Finished((Uuid, T)),
+    Partial((Uuid, T)),
     Cancelled(Uuid),
 }
 
@@ -25,48 +42,307 @@ impl<T: Serialize> CommandOutcome<T> {
         match self {
             CommandOutcome::Cancelled => UuidCommandOutcome::Cancelled(uuid),
             CommandOutcome::Finished(c) => UuidCommandOutcome::Finished((uuid, c)),
+            CommandOutcome::Partial(c) => UuidCommandOutcome::Partial((uuid, c)),
         }
     }
 }
 
-#[derive(Debug)]
-pub enum Command {
-    CancelTest(
-        i64,
-        i64,
-        oneshot::Sender<Result<CommandOutcome<i64>, ComputationError>>,
-    ),
+pub type CommandResult<T> = Result<CommandOutcome<T>, ComputationError>;
+
+/// Lists jobs, which can be called by alias with arguments in JSON (see
+/// `UnboundSessionAPI::run_by_alias`). Bindings and RPC call jobs this way, so
+/// a job listed here is available for them without any extra code.
+macro_rules! registry {
+    ($($command:ty),* $(,)?) => {
+        /// Aliases of jobs, which can be called by alias
+        pub const ALIASES: &[&str] = &[$(<$command as UnboundCommand>::ALIAS),*];
+
+        /// Calls `visitor` with the type of job, which has given alias. Returns
+        /// None if there is no such job
+        pub(crate) fn dispatch<V: Visitor>(alias: &str, visitor: V) -> Option<V::Output> {
+            $(
+                if alias == <$command as UnboundCommand>::ALIAS {
+                    return Some(visitor.visit::<$command>());
+                }
+            )*
+            None
+        }
+    };
+}
+
+registry!(
+    cancel_test::CancelTest,
+    checksum::Checksum,
+    file_format::GetFileFormat,
+    folder_content::GetFolderContent,
+    regex::GetRegexError,
+    serial_ports::GetSerialPortsList,
+);
+
+/// Receives the type of job found by `dispatch`
+pub(crate) trait Visitor {
+    type Output;
+    fn visit<C: UnboundCommand + DeserializeOwned>(self) -> Self::Output;
+}
+
+/// Declares a job, which can be executed in the scope of `UnboundSession`.
+/// Arguments of job are fields of implementor. To add a new job it's enough
+/// to implement this trait; the job can be called with `UnboundSessionAPI::run`.
+pub trait UnboundCommand: std::fmt::Debug + Send + 'static {
+    /// Results of job
+    type Output: Serialize + Clone + Send + 'static;
+    /// Alias of job, which is used to call job by name (see `registry`), in logs
+    /// and for progress tracking. Should be unique
+    const ALIAS: &'static str;
+    /// Max number of jobs of this kind, which can run at the same time.
+    /// Jobs above the limit are queued. None - no limit
+    const CONCURRENCY: Option<usize> = None;
+    /// Time after which cached results of job are considered outdated.
+    /// None - cached results are valid as long as the cache key is the same
+    const CACHE_TTL: Option<Duration> = None;
+
+    /// Key to cache results of pure jobs. Jobs of the same kind with the same key
+    /// are considered to have equal results, so repeated jobs are resolved from
+    /// cache without spawning. The key should include everything what
+    /// invalidates results (for example mtime of file). None (default) - results
+    /// aren't cached. The key is resolved once per job before the job is passed
+    /// to the session, so it may do IO (see `file_cache_key`).
+    fn cache_key(&self) -> impl Future<Output = Option<String>> + Send {
+        async { None }
+    }
+
+    /// Executes job. Implementor should listen `signal` to stop job as soon as
+    /// cancellation is requested and return `CommandOutcome::Cancelled` in this case.
+    fn run(self, signal: Signal) -> impl Future<Output = CommandResult<Self::Output>> + Send;
+}
+
+/// Type-erased job to keep jobs of different types in one queue
+trait Job: Send {
+    fn alias(&self) -> &'static str;
+    fn concurrency(&self) -> Option<usize>;
+    fn cache_ttl(&self) -> Option<Duration>;
+    /// Runs job and sends results. Returns false if results cannot be delivered
+    /// and a copy of results if `cache` is true and job is finished.
+    fn process(
+        self: Box<Self>,
+        signal: Signal,
+        cache: bool,
+    ) -> BoxFuture<'static, (bool, Option<CachedValue>)>;
+    /// true if cached value has type of results of job
+    fn is_resolvable(&self, cached: &CachedValue) -> bool;
+    /// Sends cached value as results of job; returns false if results cannot be delivered
+    fn resolve(self: Box<Self>, cached: &CachedValue) -> bool;
+    /// Sends error instead of results; returns false if error cannot be delivered
+    fn err(self: Box<Self>, err: ComputationError) -> bool;
+    /// Reports job as cancelled without running it; returns false if outcome cannot be delivered
+    fn cancel(self: Box<Self>) -> bool;
+}
+
+struct PendingJob<C: UnboundCommand> {
+    command: C,
+    tx: oneshot::Sender<CommandResult<C::Output>>,
+}
+
+impl<C: UnboundCommand> Job for PendingJob<C> {
+    fn alias(&self) -> &'static str {
+        C::ALIAS
+    }
+
+    fn concurrency(&self) -> Option<usize> {
+        C::CONCURRENCY
+    }
+
+    fn cache_ttl(&self) -> Option<Duration> {
+        C::CACHE_TTL
+    }
+
+    fn process(
+        self: Box<Self>,
+        signal: Signal,
+        cache: bool,
+    ) -> BoxFuture<'static, (bool, Option<CachedValue>)> {
+        let PendingJob { command, tx } = *self;
+        async move {
+            let results = command.run(signal).await;
+            let cached: Option<CachedValue> = match &results {
+                Ok(CommandOutcome::Finished(output)) if cache => Some(Box::new(output.clone())),
+                _ => None,
+            };
+            (tx.send(results).is_ok(), cached)
+        }
+        .boxed()
+    }
+
+    fn is_resolvable(&self, cached: &CachedValue) -> bool {
+        cached.downcast_ref::<C::Output>().is_some()
+    }
+
+    fn resolve(self: Box<Self>, cached: &CachedValue) -> bool {
+        match cached.downcast_ref::<C::Output>() {
+            Some(output) => self
+                .tx
+                .send(Ok(CommandOutcome::Finished(output.clone())))
+                .is_ok(),
+            None => false,
+        }
+    }
+
+    fn err(self: Box<Self>, err: ComputationError) -> bool {
+        self.tx.send(Err(err)).is_ok()
+    }
+
+    fn cancel(self: Box<Self>) -> bool {
+        self.tx.send(Ok(CommandOutcome::Cancelled)).is_ok()
+    }
+}
+
+/// Defines what to do if a job with the same id is already running or queued
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OnDuplicate {
+    /// New job is rejected with an error
+    #[default]
+    Reject,
+    /// Existing job is cancelled and new job takes its id
+    Replace,
+}
+
+pub struct Command {
+    job: Box<dyn Job>,
+    partials: Option<PartialSink>,
+    on_duplicate: OnDuplicate,
+    cache_key: Option<String>,
+}
+
+impl Command {
+    /// Wraps given job into command and returns a channel to receive results of job
+    pub fn new<C: UnboundCommand>(
+        command: C,
+    ) -> (Self, oneshot::Receiver<CommandResult<C::Output>>) {
+        let (tx, rx) = oneshot::channel();
+        (
+            Command {
+                job: Box::new(PendingJob { command, tx }),
+                partials: None,
+                on_duplicate: OnDuplicate::Reject,
+                cache_key: None,
+            },
+            rx,
+        )
+    }
+
+    /// Same as `Command::new`, but additionally returns a channel to receive
+    /// intermediate results of job (`CommandOutcome::Partial`)
+    #[allow(clippy::type_complexity)]
+    pub fn with_partials<C: UnboundCommand>(
+        command: C,
+    ) -> (
+        Self,
+        oneshot::Receiver<CommandResult<C::Output>>,
+        UnboundedReceiver<CommandOutcome<C::Output>>,
+    ) {
+        let (mut cmd, rx) = Command::new(command);
+        let (tx_partials, rx_partials) = unbounded_channel();
+        cmd.partials = Some(PartialSink::new(tx_partials));
+        (cmd, rx, rx_partials)
+    }
+
+    /// Sets the behavior in case a job with the same id already exists
+    pub fn on_duplicate(mut self, on_duplicate: OnDuplicate) -> Self {
+        self.on_duplicate = on_duplicate;
+        self
+    }
+
+    /// Sets the key to cache results of job (see `UnboundCommand::cache_key`)
+    pub fn with_cache_key(mut self, cache_key: Option<String>) -> Self {
+        self.cache_key = cache_key;
+        self
+    }
+
+    pub fn partials(&self) -> Option<PartialSink> {
+        self.partials.clone()
+    }
+
+    pub fn alias(&self) -> &'static str {
+        self.job.alias()
+    }
+
+    pub fn concurrency(&self) -> Option<usize> {
+        self.job.concurrency()
+    }
+
+    pub fn duplicate_policy(&self) -> OnDuplicate {
+        self.on_duplicate
+    }
+
+    pub fn cache_key(&self) -> Option<&str> {
+        self.cache_key.as_deref()
+    }
+
+    pub fn cache_ttl(&self) -> Option<Duration> {
+        self.job.cache_ttl()
+    }
+
+    /// Resolves job with cached results. Returns the command back if cached
+    /// value doesn't fit to the job
+    pub fn resolve(self, cached: &CachedValue) -> Result<(), Command> {
+        if !self.job.is_resolvable(cached) {
+            return Err(self);
+        }
+        let cmd = self.to_string();
+        if !self.job.resolve(cached) {
+            error!("Fail to send cached response for command: {cmd}");
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for Command {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_tuple("Command").field(&self.job.alias()).finish()
+    }
 }
 
 impl std::fmt::Display for Command {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Command::CancelTest(_, _, _) => "CancelTest",
-            }
-        )
+        write!(f, "{}", self.job.alias())
     }
 }
 
-pub async fn process(command: Command, signal: Signal) {
+/// Runs command; returns a copy of results to be cached if `cache` is true
+pub async fn process(command: Command, signal: Signal, cache: bool) -> Option<CachedValue> {
     let cmd = command.to_string();
     trace!("Processing command: {cmd}");
-    if match command {
-        Command::CancelTest(a, b, tx) => tx
-            .send(cancel_test::cancel_test(a, b, signal).await)
-            .is_err(),
-    } {
+    let (sent, cached) = command.job.process(signal, cache).await;
+    if !sent {
         error!("Fail to send response for command: {cmd}");
     }
+    cached
 }
 
-pub async fn err(command: Command, err: ComputationError) {
+pub fn err(command: Command, err: ComputationError) {
     let cmd = command.to_string();
-    if match command {
-        Command::CancelTest(_a, _b, tx) => tx.send(Err(err)).is_err(),
-    } {
+    if !command.job.err(err) {
         error!("Fail to send error response for command: {cmd}");
     }
 }
+
+pub fn cancelled(command: Command) {
+    let cmd = command.to_string();
+    if !command.job.cancel() {
+        error!("Fail to send cancellation response for command: {cmd}");
+    }
+}
+
+/// Builds cache key for jobs working with a file. Key includes the time of last
+/// modification and size of file, so cached results are invalidated as soon as
+/// file is changed. Returns None if file's metadata isn't available.
+pub async fn file_cache_key(filename: &str) -> Option<String> {
+    let metadata = tokio::fs::metadata(filename).await.ok()?;
+    let modified = metadata
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_nanos();
+    Some(format!("{filename}:{modified}:{}", metadata.len()))
+}
@@ -0,0 +1,50 @@
+use crate::unbound::{
+    commands::{CommandOutcome, CommandResult, UnboundCommand},
+    signal::Signal,
+};
+use filters::filter_as_regex;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SearchFilter {
+    pub value: String,
+    pub is_regex: bool,
+    pub ignore_case: bool,
+    pub is_word: bool,
+}
+
+pub fn as_regex(filter: &SearchFilter) -> String {
+    filter_as_regex(
+        filter.value.clone(),
+        filter.ignore_case,
+        filter.is_word,
+        filter.is_regex,
+    )
+}
+
+pub fn get_filter_error(filter: &SearchFilter) -> Option<String> {
+    Regex::new(&as_regex(filter)).map_or_else(|err| Some(err.to_string()), |_| None)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetRegexError {
+    pub filter: SearchFilter,
+}
+
+impl UnboundCommand for GetRegexError {
+    type Output = Option<String>;
+    const ALIAS: &'static str = "getRegexError";
+
+    async fn cache_key(&self) -> Option<String> {
+        Some(as_regex(&self.filter))
+    }
+
+    async fn run(self, signal: Signal) -> CommandResult<Option<String>> {
+        Ok(if signal.is_cancelling() {
+            CommandOutcome::Cancelled
+        } else {
+            CommandOutcome::Finished(get_filter_error(&self.filter))
+        })
+    }
+}
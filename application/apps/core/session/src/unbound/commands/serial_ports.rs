@@ -0,0 +1,37 @@
+use crate::{
+    events::ComputationError,
+    unbound::{
+        commands::{CommandOutcome, CommandResult, UnboundCommand},
+        signal::Signal,
+    },
+};
+use serde::{Deserialize, Serialize};
+use tokio::task;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SerialPortsList(pub Vec<String>);
+
+/// Takes no arguments; braces let it be deserialized from an empty object
+#[derive(Debug, Deserialize)]
+pub struct GetSerialPortsList {}
+
+impl UnboundCommand for GetSerialPortsList {
+    type Output = SerialPortsList;
+    const ALIAS: &'static str = "getSerialPortsList";
+
+    async fn run(self, signal: Signal) -> CommandResult<SerialPortsList> {
+        let ports = task::spawn_blocking(tokio_serial::available_ports)
+            .await
+            .map_err(|e| ComputationError::Process(format!("Fail to list serial ports: {e}")))?
+            .map_err(|e| {
+                ComputationError::IoOperation(format!("Fail to list serial ports: {e}"))
+            })?;
+        Ok(if signal.is_cancelling() {
+            CommandOutcome::Cancelled
+        } else {
+            CommandOutcome::Finished(SerialPortsList(
+                ports.into_iter().map(|port| port.port_name).collect(),
+            ))
+        })
+    }
+}
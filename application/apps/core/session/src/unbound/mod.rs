@@ -1,17 +1,20 @@
 pub mod api;
+pub mod cache;
 pub mod commands;
-mod signal;
+pub mod signal;
 
 use crate::{
     events::ComputationError,
     progress::ProgressProviderAPI,
     unbound::{
         api::{UnboundSessionAPI, API},
+        cache::{ResultsCache, DEFAULT_CACHE_CAPACITY},
+        commands::{Command, OnDuplicate},
         signal::Signal,
     },
 };
 use log::{debug, error, warn};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
@@ -20,10 +23,17 @@ pub struct UnboundSession {
     rx: Option<UnboundedReceiver<API>>,
     pub finished: CancellationToken,
     session_api: UnboundSessionAPI,
+    cache_capacity: usize,
 }
 
 impl UnboundSession {
     pub fn new() -> (Self, UnboundSessionAPI) {
+        Self::with_cache_capacity(DEFAULT_CACHE_CAPACITY)
+    }
+
+    /// Creates session with given max number of cached results of pure jobs.
+    /// Zero disables caching.
+    pub fn with_cache_capacity(cache_capacity: usize) -> (Self, UnboundSessionAPI) {
         let (tx, rx): (UnboundedSender<API>, UnboundedReceiver<API>) = unbounded_channel();
         let session_api = UnboundSessionAPI::new(tx);
         (
@@ -31,6 +41,7 @@ impl UnboundSession {
                 rx: Some(rx),
                 finished: CancellationToken::new(),
                 session_api: session_api.clone(),
+                cache_capacity,
             },
             session_api,
         )
@@ -39,92 +50,232 @@ impl UnboundSession {
     pub async fn init(&mut self) -> Result<(), ComputationError> {
         let finished = self.finished.clone();
         let mut rx = self.rx.take().ok_or(ComputationError::SessionUnavailable)?; // Error: session already running
-        let progress = ProgressProviderAPI::new()?;
-        let session_api = self.session_api.clone();
+        let mut registry = Registry::new(
+            ProgressProviderAPI::new()?,
+            self.session_api.clone(),
+            ResultsCache::new(self.cache_capacity),
+        );
         tokio::spawn(async move {
-            let mut jobs: HashMap<u64, Signal> = HashMap::new();
-            let mut uuids: HashMap<u64, Uuid> = HashMap::new();
             while let Some(api) = rx.recv().await {
-                jobs.retain(|id, signal| {
-                    let cancelled = signal.is_cancelled();
-                    if cancelled {
-                        UnboundSession::stopped(&progress, &uuids, id);
-                    }
-                    !cancelled
-                });
                 match api {
-                    API::Run(job, id) => {
-                        let signal = Signal::new(job.to_string());
-                        if jobs.contains_key(&id) {
-                            crate::unbound::commands::err(
-                                job,
-                                ComputationError::InvalidArgs(String::from(
-                                    "Job has invalid id. Id already exists.",
-                                )),
-                            )
-                            .await;
-                            continue;
-                        }
-                        jobs.insert(id, signal.clone());
-                        UnboundSession::started(&progress, job.to_string(), &mut uuids, &id);
-                        let api = session_api.clone();
-                        tokio::spawn(async move {
-                            debug!("Job {job} has been called");
-                            crate::unbound::commands::process(job, signal.clone()).await;
-                            signal.confirm();
-                            let _ = api.remove_command(id);
-                        });
-                    }
-                    API::CancelJob(id) => {
-                        if let Some(signal) = jobs.get(&id) {
-                            signal.invoke();
-                            debug!("Cancel signal has been sent to job {} ({id})", signal.alias);
-                        } else {
-                            warn!("Fail to cancel job; id {id} doesn't exist.");
-                        }
-                    }
+                    API::Run(job, id) => registry.run(job, id),
+                    API::CancelJob(id) => registry.cancel(id),
                     API::Shutdown(tx) => {
-                        jobs.iter().for_each(|(_uuid, signal)| {
-                            signal.invoke();
-                        });
-                        for (id, signal) in jobs.iter() {
-                            signal.confirmed().await;
-                            UnboundSession::stopped(&progress, &uuids, id);
-                        }
-                        jobs.clear();
+                        registry.shutdown().await;
                         if tx.send(()).is_err() {
                             error!("Fail to send shutdown confirmation");
                         }
                         break;
                     }
-                    API::Remove(id) => {
-                        if jobs.remove(&id).is_some() {
-                            UnboundSession::stopped(&progress, &uuids, &id);
+                    API::Remove(uuid) => registry.remove(uuid),
+                    API::Cache(alias, key, value, ttl) => {
+                        registry.cache.insert(alias, key, value, ttl);
+                    }
+                    API::CacheStats(tx) => {
+                        if tx.send(registry.cache.stats()).is_err() {
+                            error!("Fail to send cache stats");
                         }
                     }
+                    API::ClearCache => registry.cache.clear(),
                 }
             }
             finished.cancel();
         });
         Ok(())
     }
+}
 
-    fn started(
-        progress: &ProgressProviderAPI,
-        alias: String,
-        uuids: &mut HashMap<u64, Uuid>,
-        id: &u64,
-    ) {
+struct RunningJob {
+    id: u64,
+    alias: &'static str,
+    signal: Signal,
+}
+
+/// Keeps track of running and queued jobs
+struct Registry {
+    progress: ProgressProviderAPI,
+    session_api: UnboundSessionAPI,
+    /// All running jobs, including jobs which were replaced, but still
+    /// didn't confirm cancellation
+    running: HashMap<Uuid, RunningJob>,
+    /// Actual running job for each id
+    ids: HashMap<u64, Uuid>,
+    /// Jobs waiting for a free slot because of concurrency limit
+    queued: HashMap<&'static str, VecDeque<(u64, Command)>>,
+    /// Number of running jobs of each kind
+    load: HashMap<&'static str, usize>,
+    /// Results of pure jobs
+    cache: ResultsCache,
+}
+
+impl Registry {
+    fn new(
+        progress: ProgressProviderAPI,
+        session_api: UnboundSessionAPI,
+        cache: ResultsCache,
+    ) -> Self {
+        Self {
+            progress,
+            session_api,
+            running: HashMap::new(),
+            ids: HashMap::new(),
+            queued: HashMap::new(),
+            load: HashMap::new(),
+            cache,
+        }
+    }
+
+    fn is_running(&self, id: u64) -> bool {
+        self.ids
+            .get(&id)
+            .and_then(|uuid| self.running.get(uuid))
+            .is_some_and(|job| !job.signal.is_cancelled())
+    }
+
+    fn dequeue(&mut self, id: u64) -> Option<Command> {
+        self.queued.values_mut().find_map(|queue| {
+            queue
+                .iter()
+                .position(|(queued, _)| *queued == id)
+                .and_then(|pos| queue.remove(pos))
+                .map(|(_, job)| job)
+        })
+    }
+
+    fn run(&mut self, mut job: Command, id: u64) {
+        let queued = self
+            .queued
+            .values()
+            .flatten()
+            .any(|(queued, _)| *queued == id);
+        if self.is_running(id) || queued {
+            match job.duplicate_policy() {
+                OnDuplicate::Reject => {
+                    commands::err(
+                        job,
+                        ComputationError::InvalidArgs(String::from(
+                            "Job has invalid id. Id already exists.",
+                        )),
+                    );
+                    return;
+                }
+                OnDuplicate::Replace => {
+                    debug!("Job {job} ({id}) replaces existing job with same id");
+                    self.cancel(id);
+                }
+            }
+        }
+        let alias = job.alias();
+        if let Some(cached) = job.cache_key().and_then(|key| self.cache.get(alias, key)) {
+            match job.resolve(cached) {
+                Ok(()) => {
+                    debug!("Job {alias} ({id}) is resolved from cache");
+                    // Report cache hit to make it visible for tracker
+                    let uuid = Uuid::new_v4();
+                    self.progress.started(&format!("{alias} (cached)"), &uuid);
+                    self.progress.stopped(&uuid);
+                    return;
+                }
+                Err(not_resolved) => {
+                    error!("Cached results of job {alias} have unexpected type");
+                    job = not_resolved;
+                }
+            }
+        }
+        let load = self.load.get(alias).copied().unwrap_or(0);
+        if job.concurrency().is_some_and(|limit| load >= limit) {
+            debug!("Job {job} ({id}) is queued; limit of concurrent jobs is reached");
+            self.queued.entry(alias).or_default().push_back((id, job));
+        } else {
+            self.spawn(job, id);
+        }
+    }
+
+    fn spawn(&mut self, job: Command, id: u64) {
+        let alias = job.alias();
         let uuid = Uuid::new_v4();
-        uuids.insert(*id, uuid);
-        progress.started(&alias, &uuid);
+        self.progress.started(alias, &uuid);
+        let signal = Signal::new(alias.to_string())
+            .with_progress(self.progress.clone(), uuid)
+            .with_partials(job.partials());
+        self.running.insert(
+            uuid,
+            RunningJob {
+                id,
+                alias,
+                signal: signal.clone(),
+            },
+        );
+        self.ids.insert(id, uuid);
+        *self.load.entry(alias).or_default() += 1;
+        let api = self.session_api.clone();
+        let cache_key = job.cache_key().map(str::to_owned);
+        let cache_ttl = job.cache_ttl();
+        tokio::spawn(async move {
+            debug!("Job {job} has been called");
+            let cached = commands::process(job, signal.clone(), cache_key.is_some()).await;
+            signal.confirm();
+            if let (Some(key), Some(value)) = (cache_key, cached) {
+                let _ = api.cache_results(alias, key, value, cache_ttl);
+            }
+            let _ = api.remove_command(uuid);
+        });
     }
 
-    fn stopped(progress: &ProgressProviderAPI, uuids: &HashMap<u64, Uuid>, id: &u64) {
-        if let Some(uuid) = uuids.get(id) {
-            progress.stopped(uuid);
+    fn cancel(&mut self, id: u64) {
+        if let Some(job) = self
+            .ids
+            .remove(&id)
+            .and_then(|uuid| self.running.get(&uuid))
+        {
+            job.signal.invoke();
+            debug!("Cancel signal has been sent to job {} ({id})", job.alias);
+        } else if let Some(job) = self.dequeue(id) {
+            debug!("Job {job} ({id}) is removed from queue");
+            commands::cancelled(job);
         } else {
-            error!("Fail to find UUID for operation id={id}");
+            warn!("Fail to cancel job; id {id} doesn't exist.");
+        }
+    }
+
+    /// Removes finished job and starts queued jobs of the same kind if possible
+    fn remove(&mut self, uuid: Uuid) {
+        let Some(job) = self.running.remove(&uuid) else {
+            error!("Fail to find job with UUID={uuid}");
+            return;
+        };
+        self.progress.stopped(&uuid);
+        if self.ids.get(&job.id) == Some(&uuid) {
+            self.ids.remove(&job.id);
+        }
+        if let Some(load) = self.load.get_mut(job.alias) {
+            *load = load.saturating_sub(1);
+        }
+        while let Some((id, next)) = self.queued.get_mut(job.alias).and_then(|q| q.pop_front()) {
+            let load = self.load.get(job.alias).copied().unwrap_or(0);
+            if next.concurrency().is_some_and(|limit| load >= limit) {
+                self.queued
+                    .entry(job.alias)
+                    .or_default()
+                    .push_front((id, next));
+                break;
+            }
+            self.spawn(next, id);
+        }
+    }
+
+    async fn shutdown(&mut self) {
+        for (_, queue) in self.queued.drain() {
+            queue
+                .into_iter()
+                .for_each(|(_, job)| commands::cancelled(job));
+        }
+        self.running.values().for_each(|job| job.signal.invoke());
+        for (uuid, job) in self.running.drain() {
+            job.signal.confirmed().await;
+            self.progress.stopped(&uuid);
         }
+        self.ids.clear();
+        self.load.clear();
     }
 }
@@ -1,10 +1,34 @@
+use crate::{events::Ticks, progress::ProgressProviderAPI, unbound::commands::CommandOutcome};
+use log::error;
+use std::{any::Any, sync::Arc};
+use tokio::sync::mpsc::UnboundedSender;
 use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// Type-erased channel to deliver intermediate results of job. Type of results
+/// is known only by the job itself, see `Signal::partial`
+#[derive(Clone)]
+pub struct PartialSink(Arc<dyn Any + Send + Sync>);
+
+impl PartialSink {
+    pub fn new<T: Send + 'static>(tx: UnboundedSender<CommandOutcome<T>>) -> Self {
+        Self(Arc::new(tx))
+    }
+}
+
+impl std::fmt::Debug for PartialSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "PartialSink")
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct Signal {
     pub alias: String,
     signal: CancellationToken,
     confirmation: CancellationToken,
+    progress: Option<(ProgressProviderAPI, Uuid)>,
+    partials: Option<PartialSink>,
 }
 
 impl Signal {
@@ -13,9 +37,23 @@ impl Signal {
             alias,
             signal: CancellationToken::new(),
             confirmation: CancellationToken::new(),
+            progress: None,
+            partials: None,
         }
     }
 
+    /// Job would report progress with given provider under given uuid
+    pub fn with_progress(mut self, progress: ProgressProviderAPI, uuid: Uuid) -> Self {
+        self.progress = Some((progress, uuid));
+        self
+    }
+
+    /// Job would be able to deliver intermediate results into given sink
+    pub fn with_partials(mut self, partials: Option<PartialSink>) -> Self {
+        self.partials = partials;
+        self
+    }
+
     pub fn token(&self) -> CancellationToken {
         self.signal.clone()
     }
@@ -51,4 +89,45 @@ impl Signal {
         }
         self.confirmation.cancel();
     }
+
+    /// Reports progress of job to the progress tracker
+    pub fn progress(&self, count: u64, total: Option<u64>, state: Option<String>) {
+        if let Some((progress, uuid)) = self.progress.as_ref() {
+            progress.progress(
+                uuid,
+                Ticks {
+                    count,
+                    total,
+                    state,
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    /// true if the caller of job listens for intermediate results
+    pub fn has_partials(&self) -> bool {
+        self.partials.is_some()
+    }
+
+    /// Delivers intermediate results of job. Returns false if nobody listens
+    /// for intermediate results or the type of results doesn't match to the
+    /// expected one.
+    pub fn partial<T: Send + 'static>(&self, value: T) -> bool {
+        let Some(partials) = self.partials.as_ref() else {
+            return false;
+        };
+        if let Some(tx) = partials
+            .0
+            .downcast_ref::<UnboundedSender<CommandOutcome<T>>>()
+        {
+            tx.send(CommandOutcome::Partial(value)).is_ok()
+        } else {
+            error!(
+                "Job {} tries to send intermediate results of unexpected type",
+                self.alias
+            );
+            false
+        }
+    }
 }
@@ -0,0 +1,66 @@
+#![allow(dead_code)]
+
+use session::events::CallbackEvent;
+use std::time::Duration;
+use tokio::{sync::mpsc::UnboundedReceiver, time::timeout};
+use uuid::Uuid;
+
+/// Max time to wait for an expected event
+pub const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Returns next event or None if channel is closed. Panics on timeout
+pub async fn next_event(rx: &mut UnboundedReceiver<CallbackEvent>) -> Option<CallbackEvent> {
+    timeout(TIMEOUT, rx.recv())
+        .await
+        .expect("timeout while waiting for event")
+}
+
+/// Waits for the final event (done or error) of the given operation. Events
+/// of other operations are skipped. Panics on timeout
+pub async fn finished(rx: &mut UnboundedReceiver<CallbackEvent>, operation: Uuid) -> CallbackEvent {
+    timeout(TIMEOUT, settled(rx, operation))
+        .await
+        .expect("timeout while waiting for operation")
+}
+
+/// Same as `finished`, but without timeout. Should be used with paused time,
+/// where a timeout would be fired as soon as runtime is idle.
+pub async fn settled(rx: &mut UnboundedReceiver<CallbackEvent>, operation: Uuid) -> CallbackEvent {
+    loop {
+        match rx.recv().await {
+            Some(CallbackEvent::OperationDone(done)) if done.uuid == operation => {
+                return CallbackEvent::OperationDone(done)
+            }
+            Some(CallbackEvent::OperationError { uuid, error }) if uuid == operation => {
+                return CallbackEvent::OperationError { uuid, error }
+            }
+            Some(_) => {}
+            None => panic!("channel is closed before operation {operation} is finished"),
+        }
+    }
+}
+
+/// Waits until channel of events is closed; returns all received events
+pub async fn closed(rx: &mut UnboundedReceiver<CallbackEvent>) -> Vec<CallbackEvent> {
+    let mut events = Vec::new();
+    while let Some(event) = next_event(rx).await {
+        events.push(event);
+    }
+    events
+}
+
+/// Parses results of sleep operation
+pub fn slept_well(event: &CallbackEvent) -> bool {
+    match event {
+        CallbackEvent::OperationDone(done) => {
+            let result = done
+                .result
+                .as_ref()
+                .expect("sleep operation should have results");
+            result["sleep_well"]
+                .as_bool()
+                .expect("sleep_well should be boolean")
+        }
+        other => panic!("expected OperationDone, got {other}"),
+    }
+}
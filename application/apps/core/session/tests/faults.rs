@@ -0,0 +1,106 @@
+mod common;
+
+use common::{closed, finished, next_event, settled, slept_well};
+use session::{
+    events::{CallbackEvent, ComputationError, NativeErrorKind},
+    faults::{Fault, Faults, Loop},
+    session::Session,
+};
+use tokio::time::{Duration, Instant};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn drop_tracker_channel() {
+    let faults = Faults::default().inject(Loop::Tracker, 1, Fault::DropChannel);
+    let (session, mut rx) = Session::with_faults(Uuid::new_v4(), faults.clone())
+        .await
+        .unwrap();
+    let operation = session.sleep(None, 10).unwrap();
+    match finished(&mut rx, operation).await {
+        CallbackEvent::OperationError { error, .. } => {
+            assert_eq!(error.kind, NativeErrorKind::ChannelError)
+        }
+        other => panic!("expected OperationError, got {other}"),
+    }
+    assert_eq!(faults.received(Loop::Tracker), 1);
+    // Session without tracker still can be stopped
+    session.stop(None).await.unwrap();
+    closed(&mut rx).await;
+}
+
+#[tokio::test]
+async fn fail_tracker_destroys_session() {
+    let faults = Faults::default().inject(Loop::Tracker, 1, Fault::Fail);
+    let (session, mut rx) = Session::with_faults(Uuid::new_v4(), faults).await.unwrap();
+    let operation = session.sleep(None, 10).unwrap();
+    let events = closed(&mut rx).await;
+    assert!(events.iter().any(|event| matches!(
+        event,
+        CallbackEvent::OperationError { uuid, .. } if *uuid == operation
+    )));
+    assert!(matches!(
+        session.sleep(None, 10),
+        Err(ComputationError::Communication(_))
+    ));
+    other_sessions_arent_affected().await;
+}
+
+#[tokio::test]
+async fn fail_state_destroys_session() {
+    let faults = Faults::default().inject(Loop::State, 1, Fault::Fail);
+    let (session, mut rx) = Session::with_faults(Uuid::new_v4(), faults).await.unwrap();
+    session.state.set_debug(true).await.unwrap_err();
+    closed(&mut rx).await;
+    other_sessions_arent_affected().await;
+}
+
+async fn other_sessions_arent_affected() {
+    let (session, mut rx) = Session::new(Uuid::new_v4()).await.unwrap();
+    let operation = session.sleep(None, 10).unwrap();
+    assert!(slept_well(&finished(&mut rx, operation).await));
+    session.stop(None).await.unwrap();
+}
+
+#[tokio::test]
+async fn fail_nth_operation() {
+    let faults = Faults::default().inject(Loop::Operations, 2, Fault::Fail);
+    let (session, mut rx) = Session::with_faults(Uuid::new_v4(), faults.clone())
+        .await
+        .unwrap();
+    let operation = session.sleep(None, 10).unwrap();
+    assert!(slept_well(&finished(&mut rx, operation).await));
+    session.sleep(None, 10).unwrap();
+    assert!(matches!(
+        next_event(&mut rx).await,
+        Some(CallbackEvent::SessionError(error)) if error.kind == NativeErrorKind::Io
+    ));
+    closed(&mut rx).await;
+    assert_eq!(faults.received(Loop::Operations), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn delay_tracker_response() {
+    let faults = Faults::default().inject(Loop::Tracker, 1, Fault::Delay(Duration::from_secs(30)));
+    let (session, mut rx) = Session::with_faults(Uuid::new_v4(), faults).await.unwrap();
+    let started = Instant::now();
+    let operation = session.sleep(None, 1_000).unwrap();
+    assert!(slept_well(&settled(&mut rx, operation).await));
+    assert!(started.elapsed() >= Duration::from_secs(31));
+    session.stop(None).await.unwrap();
+}
+
+#[tokio::test(start_paused = true)]
+async fn delayed_operation_is_aborted() {
+    let faults =
+        Faults::default().inject(Loop::Operations, 2, Fault::Delay(Duration::from_secs(60)));
+    let (session, mut rx) = Session::with_faults(Uuid::new_v4(), faults).await.unwrap();
+    let operation = session.sleep(None, 10_000).unwrap();
+    // Abort reaches operations loop only after 60s, when sleep is already done
+    let abort = session.abort(None, operation).unwrap();
+    assert!(slept_well(&settled(&mut rx, operation).await));
+    assert!(matches!(
+        settled(&mut rx, abort).await,
+        CallbackEvent::OperationError { .. }
+    ));
+    session.stop(None).await.unwrap();
+}
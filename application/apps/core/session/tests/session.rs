@@ -0,0 +1,215 @@
+mod common;
+
+use common::{closed, finished, next_event, settled, slept_well, TIMEOUT};
+use session::{
+    events::{CallbackEvent, ComputationError, NativeErrorKind, Severity},
+    session::Session,
+};
+use tokio::time::{timeout, Duration, Instant};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn new_and_stop() {
+    let uuid = Uuid::new_v4();
+    let (session, mut rx) = Session::new(uuid).await.expect("session is created");
+    assert_eq!(session.get_uuid(), uuid);
+    timeout(TIMEOUT, session.stop(None))
+        .await
+        .expect("session is stopped in time")
+        .expect("session is stopped");
+    closed(&mut rx).await;
+}
+
+#[tokio::test]
+async fn operations_after_stop_are_rejected() {
+    let (session, mut rx) = Session::new(Uuid::new_v4()).await.unwrap();
+    session.stop(None).await.unwrap();
+    closed(&mut rx).await;
+    assert!(matches!(
+        session.sleep(None, 10),
+        Err(ComputationError::Communication(_))
+    ));
+}
+
+#[tokio::test]
+async fn allocated_operation_id() {
+    let (session, mut rx) = Session::new(Uuid::new_v4()).await.unwrap();
+    let operation = session.sleep(None, 10).unwrap();
+    assert!(matches!(
+        next_event(&mut rx).await,
+        Some(CallbackEvent::OperationStarted(uuid)) if uuid == operation
+    ));
+    assert!(slept_well(&finished(&mut rx, operation).await));
+    session.stop(None).await.unwrap();
+}
+
+/// Encodes event the same way as the MessagePack encoding of bindings does
+fn to_msgpack(event: &CallbackEvent) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    serde::Serialize::serialize(
+        event,
+        &mut rmp_serde::Serializer::new(&mut bytes)
+            .with_struct_map()
+            .with_human_readable(),
+    )
+    .expect("event is encoded");
+    bytes
+}
+
+#[tokio::test]
+async fn results_round_trip_msgpack() {
+    let (session, mut rx) = Session::new(Uuid::new_v4()).await.unwrap();
+    let operation = session.sleep(None, 10).unwrap();
+    let done = finished(&mut rx, operation).await;
+    let bytes = to_msgpack(&done);
+    // Results are a part of the structure, not a JSON string inside of it
+    assert!(!bytes
+        .windows(b"sleep_well\"".len())
+        .any(|w| w == b"sleep_well\""));
+    let decoded: CallbackEvent = serde::Deserialize::deserialize(
+        &mut rmp_serde::Deserializer::new(&bytes[..]).with_human_readable(),
+    )
+    .expect("event is decoded");
+    assert!(slept_well(&decoded));
+    match decoded {
+        CallbackEvent::OperationDone(decoded) => assert_eq!(decoded.uuid, operation),
+        other => panic!("expected OperationDone, got {other}"),
+    }
+    let json: CallbackEvent =
+        serde_json::from_str(&serde_json::to_string(&done).unwrap()).expect("event is parsed");
+    assert!(slept_well(&json));
+    session.stop(None).await.unwrap();
+}
+
+#[tokio::test]
+async fn abort_running_operation() {
+    let (session, mut rx) = Session::new(Uuid::new_v4()).await.unwrap();
+    let operation = session.sleep(None, 60_000).unwrap();
+    let abort = session.abort(None, operation).unwrap();
+    assert!(!slept_well(&finished(&mut rx, operation).await));
+    assert!(matches!(
+        finished(&mut rx, abort).await,
+        CallbackEvent::OperationDone(_)
+    ));
+    session.stop(None).await.unwrap();
+}
+
+#[tokio::test]
+async fn abort_unknown_operation() {
+    let (session, mut rx) = Session::new(Uuid::new_v4()).await.unwrap();
+    let abort = session.abort(None, Uuid::new_v4()).unwrap();
+    match finished(&mut rx, abort).await {
+        CallbackEvent::OperationError { error, .. } => {
+            assert_eq!(error.severity, Severity::WARNING)
+        }
+        other => panic!("expected OperationError, got {other}"),
+    }
+    session.stop(None).await.unwrap();
+}
+
+#[tokio::test]
+async fn abort_races_with_finishing() {
+    let (session, mut rx) = Session::new(Uuid::new_v4()).await.unwrap();
+    for _ in 0..20 {
+        let operation = session.sleep(None, 0).unwrap();
+        let abort = session.abort(None, operation).unwrap();
+        // Regardless of who wins, both operations have to be finished
+        let mut pending = vec![operation, abort];
+        while !pending.is_empty() {
+            match next_event(&mut rx).await.expect("channel is open") {
+                CallbackEvent::OperationDone(done) => pending.retain(|uuid| *uuid != done.uuid),
+                CallbackEvent::OperationError { uuid, error } => {
+                    assert_ne!(uuid, operation, "operation is failed: {error:?}");
+                    assert_eq!(error.severity, Severity::WARNING);
+                    pending.retain(|pending| *pending != uuid);
+                }
+                _ => {}
+            }
+        }
+    }
+    session.stop(None).await.unwrap();
+}
+
+#[tokio::test]
+async fn stop_with_running_operation() {
+    let (session, mut rx) = Session::new(Uuid::new_v4()).await.unwrap();
+    let operation = session.sleep(None, 60_000).unwrap();
+    assert!(matches!(
+        next_event(&mut rx).await,
+        Some(CallbackEvent::OperationStarted(uuid)) if uuid == operation
+    ));
+    timeout(TIMEOUT, session.stop(None))
+        .await
+        .expect("session is stopped in time")
+        .unwrap();
+    closed(&mut rx).await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn sleep_with_paused_time() {
+    let (session, mut rx) = Session::new(Uuid::new_v4()).await.unwrap();
+    let started = Instant::now();
+    let operation = session.sleep(None, 3_600_000).unwrap();
+    assert!(slept_well(&settled(&mut rx, operation).await));
+    assert!(started.elapsed() >= Duration::from_secs(3_600));
+    session.stop(None).await.unwrap();
+}
+
+#[tokio::test]
+async fn duplicate_operation_id() {
+    let (session, mut rx) = Session::new(Uuid::new_v4()).await.unwrap();
+    let operation = Uuid::new_v4();
+    assert_eq!(session.sleep(Some(operation), 200).unwrap(), operation);
+    assert_eq!(session.sleep(Some(operation), 200).unwrap(), operation);
+    let mut done = 0;
+    let mut rejected = 0;
+    while done + rejected < 2 {
+        match next_event(&mut rx).await.expect("channel is open") {
+            CallbackEvent::OperationDone(result) if result.uuid == operation => done += 1,
+            CallbackEvent::OperationError { uuid, error } if uuid == operation => {
+                assert_eq!(error.kind, NativeErrorKind::ComputationFailed);
+                assert!(error
+                    .message
+                    .as_deref()
+                    .is_some_and(|msg| msg.contains("already exists")));
+                rejected += 1;
+            }
+            _ => {}
+        }
+    }
+    assert_eq!((done, rejected), (1, 1));
+    session.stop(None).await.unwrap();
+}
+
+#[tokio::test]
+async fn notification_refers_to_line() {
+    let (session, mut rx) = Session::new(Uuid::new_v4()).await.unwrap();
+    let operation = session
+        .external_call_lib(
+            None,
+            String::from("not_existing_lib.so"),
+            1,
+            2,
+            vec![String::from("one"), String::from("two\nthree")],
+        )
+        .unwrap();
+    let mut notifications = Vec::new();
+    loop {
+        match next_event(&mut rx).await.expect("channel is open") {
+            CallbackEvent::SessionNotification(notification) => notifications.push(notification),
+            CallbackEvent::OperationError { uuid, error } if uuid == operation => {
+                // Diagnostics are emitted even if the library cannot be loaded
+                assert_eq!(error.kind, NativeErrorKind::ComputationFailed);
+                break;
+            }
+            CallbackEvent::OperationDone(done) if done.uuid == operation => {
+                panic!("library isn't expected to be loaded")
+            }
+            _ => {}
+        }
+    }
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].severity, Severity::WARNING);
+    assert_eq!(notifications[0].line, Some(1));
+    session.stop(None).await.unwrap();
+}
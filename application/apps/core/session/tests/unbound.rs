@@ -0,0 +1,510 @@
+use serde_json::json;
+use session::{
+    events::ComputationError,
+    unbound::{
+        api::UnboundSessionAPI,
+        commands::{
+            cancel_test::CancelTest, CommandOutcome, CommandResult, OnDuplicate, UnboundCommand,
+            ALIASES,
+        },
+        signal::Signal,
+        UnboundSession,
+    },
+};
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::{
+    select,
+    time::{sleep, timeout},
+};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn job() -> CancelTest {
+    CancelTest {
+        custom_arg_a: 1,
+        custom_arg_b: 2,
+    }
+}
+
+/// Pure job counting its runs; results are cached by `n`
+#[derive(Debug)]
+struct Square {
+    n: u64,
+    runs: Arc<AtomicUsize>,
+}
+
+impl UnboundCommand for Square {
+    type Output = u64;
+    const ALIAS: &'static str = "Square";
+
+    async fn cache_key(&self) -> Option<String> {
+        Some(self.n.to_string())
+    }
+
+    async fn run(self, _signal: Signal) -> CommandResult<u64> {
+        self.runs.fetch_add(1, Ordering::SeqCst);
+        Ok(CommandOutcome::Finished(self.n * self.n))
+    }
+}
+
+/// Same as `Square`, but cached results expire quickly
+#[derive(Debug)]
+struct FleetingSquare {
+    n: u64,
+    runs: Arc<AtomicUsize>,
+}
+
+impl UnboundCommand for FleetingSquare {
+    type Output = u64;
+    const ALIAS: &'static str = "FleetingSquare";
+    const CACHE_TTL: Option<Duration> = Some(Duration::from_millis(50));
+
+    async fn cache_key(&self) -> Option<String> {
+        Some(self.n.to_string())
+    }
+
+    async fn run(self, _signal: Signal) -> CommandResult<u64> {
+        self.runs.fetch_add(1, Ordering::SeqCst);
+        Ok(CommandOutcome::Finished(self.n * self.n))
+    }
+}
+
+/// Counts jobs running at the same time
+#[derive(Debug, Default)]
+struct Load {
+    current: AtomicUsize,
+    max: AtomicUsize,
+}
+
+/// Job limited to one running instance; returns its own number
+#[derive(Debug)]
+struct Exclusive {
+    n: usize,
+    load: Arc<Load>,
+}
+
+impl UnboundCommand for Exclusive {
+    type Output = usize;
+    const ALIAS: &'static str = "Exclusive";
+    const CONCURRENCY: Option<usize> = Some(1);
+
+    async fn run(self, signal: Signal) -> CommandResult<usize> {
+        let current = self.load.current.fetch_add(1, Ordering::SeqCst) + 1;
+        self.load.max.fetch_max(current, Ordering::SeqCst);
+        let outcome = select! {
+            _ = signal.cancelled() => CommandOutcome::Cancelled,
+            _ = sleep(Duration::from_millis(100)) => CommandOutcome::Finished(self.n),
+        };
+        self.load.current.fetch_sub(1, Ordering::SeqCst);
+        Ok(outcome)
+    }
+}
+
+#[tokio::test]
+async fn run_job() {
+    let (mut session, api) = UnboundSession::new();
+    session.init().await.unwrap();
+    assert!(matches!(
+        api.run(1, job()).await,
+        Ok(CommandOutcome::Finished(3))
+    ));
+    api.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn init_twice() {
+    let (mut session, api) = UnboundSession::new();
+    session.init().await.unwrap();
+    assert!(matches!(
+        session.init().await,
+        Err(ComputationError::SessionUnavailable)
+    ));
+    api.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn cancel_job() {
+    let (mut session, api) = UnboundSession::new();
+    session.init().await.unwrap();
+    let running = tokio::spawn({
+        let api = api.clone();
+        async move { api.run(1, job()).await }
+    });
+    sleep(Duration::from_millis(50)).await;
+    api.cancel_job(&1).await.unwrap();
+    assert!(matches!(
+        running.await.unwrap(),
+        Ok(CommandOutcome::Cancelled)
+    ));
+    api.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn duplicate_job_id() {
+    let (mut session, api) = UnboundSession::new();
+    session.init().await.unwrap();
+    let running = tokio::spawn({
+        let api = api.clone();
+        async move { api.run(1, job()).await }
+    });
+    sleep(Duration::from_millis(50)).await;
+    assert!(matches!(
+        api.run(1, job()).await,
+        Err(ComputationError::InvalidArgs(_))
+    ));
+    assert!(matches!(
+        running.await.unwrap(),
+        Ok(CommandOutcome::Finished(3))
+    ));
+    api.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn shutdown_with_jobs_in_flight() {
+    let (mut session, api) = UnboundSession::new();
+    session.init().await.unwrap();
+    let finished = session.finished.clone();
+    let jobs: Vec<_> = (0..5)
+        .map(|id| {
+            let api = api.clone();
+            tokio::spawn(async move { api.run(id, job()).await })
+        })
+        .collect();
+    sleep(Duration::from_millis(50)).await;
+    timeout(TIMEOUT, api.shutdown())
+        .await
+        .expect("session is shut down in time")
+        .unwrap();
+    for job in jobs {
+        assert!(matches!(job.await.unwrap(), Ok(CommandOutcome::Cancelled)));
+    }
+    timeout(TIMEOUT, finished.cancelled())
+        .await
+        .expect("session is finished");
+    assert!(matches!(
+        api.run(10, job()).await,
+        Err(ComputationError::Communication(_))
+    ));
+}
+
+#[tokio::test]
+async fn replace_job() {
+    let (mut session, api) = UnboundSession::new();
+    session.init().await.unwrap();
+    let replaced = tokio::spawn({
+        let api = api.clone();
+        async move { api.run(1, job()).await }
+    });
+    sleep(Duration::from_millis(50)).await;
+    assert!(matches!(
+        api.run_replacing(
+            1,
+            CancelTest {
+                custom_arg_a: 2,
+                custom_arg_b: 2,
+            }
+        )
+        .await,
+        Ok(CommandOutcome::Finished(4))
+    ));
+    assert!(matches!(
+        replaced.await.unwrap(),
+        Ok(CommandOutcome::Cancelled)
+    ));
+    api.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn replace_without_duplicate() {
+    let (mut session, api) = UnboundSession::new();
+    session.init().await.unwrap();
+    assert!(matches!(
+        api.run_replacing(1, job()).await,
+        Ok(CommandOutcome::Finished(3))
+    ));
+    api.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn concurrency_limit() {
+    let (mut session, api) = UnboundSession::new();
+    session.init().await.unwrap();
+    let load = Arc::new(Load::default());
+    let jobs: Vec<_> = (0..3)
+        .map(|n| {
+            let api = api.clone();
+            let load = load.clone();
+            tokio::spawn(async move { api.run(n as u64, Exclusive { n, load }).await })
+        })
+        .collect();
+    for (n, job) in jobs.into_iter().enumerate() {
+        match timeout(TIMEOUT, job).await.expect("job is done in time") {
+            Ok(Ok(CommandOutcome::Finished(done))) => assert_eq!(done, n),
+            other => panic!("unexpected outcome of job {n}: {other:?}"),
+        }
+    }
+    assert_eq!(load.max.load(Ordering::SeqCst), 1);
+    api.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn cancel_and_replace_queued_job() {
+    let (mut session, api) = UnboundSession::new();
+    session.init().await.unwrap();
+    let load = Arc::new(Load::default());
+    let run = |id: u64, n: usize, replace: bool| {
+        let api = api.clone();
+        let load = load.clone();
+        tokio::spawn(async move {
+            let job = Exclusive { n, load };
+            if replace {
+                api.run_replacing(id, job).await
+            } else {
+                api.run(id, job).await
+            }
+        })
+    };
+    let running = run(1, 1, false);
+    sleep(Duration::from_millis(20)).await;
+    let cancelled = run(2, 2, false);
+    let replaced = run(3, 3, false);
+    sleep(Duration::from_millis(20)).await;
+    // Both jobs are waiting for the first one
+    api.cancel_job(&2).await.unwrap();
+    let replacing = run(3, 4, true);
+    assert!(matches!(
+        cancelled.await.unwrap(),
+        Ok(CommandOutcome::Cancelled)
+    ));
+    assert!(matches!(
+        replaced.await.unwrap(),
+        Ok(CommandOutcome::Cancelled)
+    ));
+    assert!(matches!(
+        running.await.unwrap(),
+        Ok(CommandOutcome::Finished(1))
+    ));
+    assert!(matches!(
+        replacing.await.unwrap(),
+        Ok(CommandOutcome::Finished(4))
+    ));
+    assert_eq!(load.max.load(Ordering::SeqCst), 1);
+    api.shutdown().await.unwrap();
+}
+
+/// Streaming job; delivers `n` as intermediate results every 10ms until cancelled
+#[derive(Debug)]
+struct Ticker {
+    n: usize,
+}
+
+impl UnboundCommand for Ticker {
+    type Output = usize;
+    const ALIAS: &'static str = "Ticker";
+
+    async fn run(self, signal: Signal) -> CommandResult<usize> {
+        for _ in 0..3 {
+            signal.partial(self.n);
+            select! {
+                _ = signal.cancelled() => return Ok(CommandOutcome::Cancelled),
+                _ = sleep(Duration::from_millis(10)) => {}
+            }
+        }
+        Ok(CommandOutcome::Finished(self.n))
+    }
+}
+
+#[tokio::test]
+async fn replace_streaming_job() {
+    let (mut session, api) = UnboundSession::new();
+    session.init().await.unwrap();
+    let stream = |n: usize, on_duplicate: OnDuplicate| {
+        let api = api.clone();
+        tokio::spawn(async move {
+            let partials = Arc::new(AtomicUsize::new(0));
+            let counter = partials.clone();
+            let outcome = api
+                .run_with_partials(1, Ticker { n }, on_duplicate, move |partial| {
+                    assert!(matches!(partial, CommandOutcome::Partial(p) if p == n));
+                    counter.fetch_add(1, Ordering::SeqCst);
+                })
+                .await;
+            (outcome, partials.load(Ordering::SeqCst))
+        })
+    };
+    let replaced = stream(1, OnDuplicate::Reject);
+    sleep(Duration::from_millis(15)).await;
+    let replacing = stream(2, OnDuplicate::Replace);
+    match timeout(TIMEOUT, replaced)
+        .await
+        .expect("job is done in time")
+    {
+        Ok((Ok(CommandOutcome::Cancelled), partials)) => assert!(partials >= 1),
+        other => panic!("unexpected outcome of replaced job: {other:?}"),
+    }
+    match timeout(TIMEOUT, replacing)
+        .await
+        .expect("job is done in time")
+    {
+        Ok((Ok(CommandOutcome::Finished(2)), partials)) => assert_eq!(partials, 3),
+        other => panic!("unexpected outcome of replacing job: {other:?}"),
+    }
+    api.shutdown().await.unwrap();
+}
+
+async fn square(api: &UnboundSessionAPI, id: u64, n: u64, runs: &Arc<AtomicUsize>) -> u64 {
+    let runs = runs.clone();
+    match api.run(id, Square { n, runs }).await {
+        Ok(CommandOutcome::Finished(square)) => square,
+        other => panic!("unexpected outcome: {other:?}"),
+    }
+}
+
+/// Results of jobs are cached after the job is removed from registry, which
+/// happens right after results are sent
+async fn settle() {
+    sleep(Duration::from_millis(20)).await;
+}
+
+#[tokio::test]
+async fn cache_hit_and_miss() {
+    let (mut session, api) = UnboundSession::new();
+    session.init().await.unwrap();
+    let runs = Arc::new(AtomicUsize::new(0));
+    assert_eq!(square(&api, 1, 3, &runs).await, 9);
+    settle().await;
+    assert_eq!(square(&api, 2, 3, &runs).await, 9);
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+    assert_eq!(square(&api, 3, 4, &runs).await, 16);
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+    let stats = api.cache_stats().await.unwrap();
+    assert_eq!((stats.hits, stats.misses), (1, 2));
+    settle().await;
+    assert_eq!(api.cache_stats().await.unwrap().entries, 2);
+    api.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn cache_ttl() {
+    let (mut session, api) = UnboundSession::new();
+    session.init().await.unwrap();
+    let runs = Arc::new(AtomicUsize::new(0));
+    let fleeting = |n: u64| FleetingSquare {
+        n,
+        runs: runs.clone(),
+    };
+    assert!(matches!(
+        api.run(1, fleeting(2)).await,
+        Ok(CommandOutcome::Finished(4))
+    ));
+    settle().await;
+    assert!(matches!(
+        api.run(2, fleeting(2)).await,
+        Ok(CommandOutcome::Finished(4))
+    ));
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+    sleep(Duration::from_millis(100)).await;
+    assert!(matches!(
+        api.run(3, fleeting(2)).await,
+        Ok(CommandOutcome::Finished(4))
+    ));
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+    api.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn cache_lru_eviction() {
+    let (mut session, api) = UnboundSession::with_cache_capacity(2);
+    session.init().await.unwrap();
+    let runs = Arc::new(AtomicUsize::new(0));
+    for (id, n) in [1, 2].into_iter().enumerate() {
+        square(&api, id as u64, n, &runs).await;
+        settle().await;
+    }
+    // 1 is used recently, so 2 is evicted by 3
+    square(&api, 10, 1, &runs).await;
+    square(&api, 11, 3, &runs).await;
+    settle().await;
+    assert_eq!(runs.load(Ordering::SeqCst), 3);
+    assert_eq!(api.cache_stats().await.unwrap().entries, 2);
+    square(&api, 12, 1, &runs).await;
+    assert_eq!(runs.load(Ordering::SeqCst), 3);
+    square(&api, 13, 2, &runs).await;
+    assert_eq!(runs.load(Ordering::SeqCst), 4);
+    api.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn cache_disabled() {
+    let (mut session, api) = UnboundSession::with_cache_capacity(0);
+    session.init().await.unwrap();
+    let runs = Arc::new(AtomicUsize::new(0));
+    square(&api, 1, 2, &runs).await;
+    settle().await;
+    square(&api, 2, 2, &runs).await;
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+    assert_eq!(api.cache_stats().await.unwrap().entries, 0);
+    api.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn clear_cache() {
+    let (mut session, api) = UnboundSession::new();
+    session.init().await.unwrap();
+    let runs = Arc::new(AtomicUsize::new(0));
+    square(&api, 1, 5, &runs).await;
+    settle().await;
+    assert_eq!(api.cache_stats().await.unwrap().entries, 1);
+    api.clear_cache().unwrap();
+    let stats = api.cache_stats().await.unwrap();
+    assert_eq!(stats.entries, 0);
+    // Counters are kept
+    assert_eq!(stats.misses, 1);
+    square(&api, 2, 5, &runs).await;
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+    api.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn run_by_alias() {
+    let (mut session, api) = UnboundSession::new();
+    session.init().await.unwrap();
+    let args = json!({"custom_arg_a": 2, "custom_arg_b": 3});
+    assert!(matches!(
+        api.run_by_alias(1, CancelTest::ALIAS, args, OnDuplicate::Reject).await,
+        Ok(CommandOutcome::Finished(value)) if value == json!(5)
+    ));
+    assert!(matches!(
+        api.run_by_alias(2, "unknown", json!({}), OnDuplicate::Reject)
+            .await,
+        Err(ComputationError::InvalidArgs(_))
+    ));
+    assert!(matches!(
+        api.run_by_alias(
+            3,
+            CancelTest::ALIAS,
+            json!({"custom_arg_a": 2}),
+            OnDuplicate::Reject
+        )
+        .await,
+        Err(ComputationError::InvalidArgs(_))
+    ));
+    assert!(matches!(
+        api.run_by_alias(4, "getSerialPortsList", json!({}), OnDuplicate::Reject)
+            .await,
+        Ok(CommandOutcome::Finished(value)) if value.is_array()
+    ));
+    api.shutdown().await.unwrap();
+}
+
+#[test]
+fn aliases_are_unique() {
+    assert_eq!(ALIASES.iter().collect::<HashSet<_>>().len(), ALIASES.len());
+}
@@ -150,4 +150,38 @@ describe('test', function () {
         const tag: string = 'p';
         test(expected, query, tag);
     });
+
+    it('should rank', () => {
+        const { Matcher } = wasm;
+        const matcher = Matcher.new();
+        matcher.set_items([
+            { name: 'very_large_file.dlt' },
+            { name: 'medium_sized_file.txt' },
+            { name: 'small_file.log' },
+        ]);
+        matcher.search('mel');
+        expect(Array.from(matcher.get_ranked(0))).toEqual([1, 2]);
+        matcher.set_max_results(1);
+        expect(Array.from(matcher.get_ranked(0))).toEqual([1]);
+        matcher.set_max_results(undefined);
+        matcher.search('');
+        expect(matcher.ranked_len()).toBe(3);
+    });
+
+    it('should keep values of items', () => {
+        const { Matcher } = wasm;
+        const matcher = Matcher.with_options(JSON.stringify({ id: 'path' }));
+        const from = matcher.set_items([
+            { path: '/a.dlt', size: 2048, meta: { kind: 'dlt' } },
+            { path: '/b.dlt', size: 512 },
+        ]);
+        expect(from).toBe(0);
+        expect(matcher.get_value_of(0, 'size')).toBe(2048);
+        expect(matcher.get_value_of(0, 'meta')).toEqual({ kind: 'dlt' });
+        expect(matcher.get_value_of(1, 'meta')).toBeUndefined();
+        matcher.search('512');
+        expect(Array.from(matcher.get_ranked(0))).toEqual([matcher.index_of('/b.dlt')]);
+        expect(() => matcher.set_items([{ path: '/a.dlt' }])).toThrow();
+        expect(() => matcher.set_items('[]')).toThrow();
+    });
 });
@@ -0,0 +1,221 @@
+use crate::query::{Anchor, Kind, Term};
+use fuzzy_matcher::{clangd::ClangdMatcher, skim::SkimMatcherV2, FuzzyMatcher};
+use regex::{Regex, RegexBuilder};
+use serde::Deserialize;
+
+/// Bonus for each matched char of exact, prefix and regex matches
+const MATCHED_CHAR_SCORE: i64 = 16;
+/// Bonus for match at the beginning of value; decreases with position of match
+const START_SCORE: i64 = 64;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Algorithm {
+    /// Fuzzy matching of skim (default)
+    #[default]
+    Skim,
+    /// Fuzzy matching of clangd
+    Clangd,
+    /// Query is a substring of value
+    Exact,
+    /// Value starts with query
+    Prefix,
+    /// Query is a regular expression
+    Regex,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Case {
+    /// Case sensitive only if query has uppercase chars (default)
+    #[default]
+    Smart,
+    Ignore,
+    Respect,
+}
+
+impl Case {
+    fn is_sensitive(&self, query: &str) -> bool {
+        match self {
+            Case::Smart => query.chars().any(char::is_uppercase),
+            Case::Ignore => false,
+            Case::Respect => true,
+        }
+    }
+}
+
+/// Options of `Matcher`, e.g. `{"algorithm": "prefix", "case": "ignore"}`
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Options {
+    pub algorithm: Algorithm,
+    pub case: Case,
+    /// Name of field with unique id of item. Id can be resolved into the
+    /// current index of item with `Matcher::index_of`
+    pub id: Option<String>,
+}
+
+/// Matches values with query according to selected algorithm
+pub enum Engine {
+    Fuzzy {
+        matcher: Box<dyn FuzzyMatcher>,
+        case: Case,
+    },
+    Exact {
+        case: Case,
+        prefix: bool,
+    },
+    /// Regex is compiled once per query (see `Engine::prepare`)
+    Regex {
+        case: Case,
+        regex: Option<Regex>,
+    },
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Engine::new(Options::default())
+    }
+}
+
+impl Engine {
+    pub fn new(options: Options) -> Self {
+        let Options {
+            algorithm, case, ..
+        } = options;
+        match algorithm {
+            Algorithm::Skim => {
+                let matcher = SkimMatcherV2::default();
+                Engine::Fuzzy {
+                    matcher: Box::new(match case {
+                        Case::Smart => matcher.smart_case(),
+                        Case::Ignore => matcher.ignore_case(),
+                        Case::Respect => matcher.respect_case(),
+                    }),
+                    case,
+                }
+            }
+            Algorithm::Clangd => {
+                let matcher = ClangdMatcher::default();
+                Engine::Fuzzy {
+                    matcher: Box::new(match case {
+                        Case::Smart => matcher.smart_case(),
+                        Case::Ignore => matcher.ignore_case(),
+                        Case::Respect => matcher.respect_case(),
+                    }),
+                    case,
+                }
+            }
+            Algorithm::Exact => Engine::Exact {
+                case,
+                prefix: false,
+            },
+            Algorithm::Prefix => Engine::Exact { case, prefix: true },
+            Algorithm::Regex => Engine::Regex { case, regex: None },
+        }
+    }
+
+    /// Should be called on each new query before matching. Invalid regular
+    /// expression doesn't match anything.
+    pub fn prepare(&mut self, query: &str) {
+        if let Engine::Regex { case, regex } = self {
+            *regex = RegexBuilder::new(query)
+                .case_insensitive(!case.is_sensitive(query))
+                .build()
+                .ok();
+        }
+    }
+
+    /// true if query operators (see `Query::parse`) are supported. Regular
+    /// expressions take the whole query, including spaces.
+    pub fn has_operators(&self) -> bool {
+        !matches!(self, Engine::Regex { .. })
+    }
+
+    /// true if a query extending the previous query can match only values
+    /// matched by the previous query
+    pub fn is_incremental(&self) -> bool {
+        !matches!(self, Engine::Regex { .. })
+    }
+
+    /// Returns score and positions of matched chars (not bytes) of value
+    pub fn indices(&self, value: &str, query: &str) -> Option<(i64, Vec<usize>)> {
+        match self {
+            Engine::Fuzzy { matcher, .. } => matcher.fuzzy_indices(value, query),
+            Engine::Exact { case, prefix } => {
+                let anchor = if *prefix { Anchor::Start } else { Anchor::None };
+                exact(value, query, *case, anchor)
+            }
+            Engine::Regex { regex, .. } => {
+                let regex = regex.as_ref()?;
+                let mut indices: Vec<usize> = Vec::new();
+                let mut first: Option<usize> = None;
+                for found in regex.find_iter(value).filter(|found| !found.is_empty()) {
+                    let start = value[..found.start()].chars().count();
+                    let len = found.as_str().chars().count();
+                    first.get_or_insert(start);
+                    indices.extend(start..start + len);
+                }
+                first.map(|start| (positional_score(start, indices.len()), indices))
+            }
+        }
+    }
+
+    /// Same as `indices`, but for a term of query. Fuzzy terms are matched with
+    /// the algorithm of engine, exact terms - with its case mode.
+    pub fn term_indices(&self, value: &str, term: &Term) -> Option<(i64, Vec<usize>)> {
+        match term.kind {
+            Kind::Fuzzy => self.indices(value, &term.text),
+            Kind::Exact(anchor) => exact(value, &term.text, self.case(), anchor),
+        }
+    }
+
+    fn case(&self) -> Case {
+        match self {
+            Engine::Fuzzy { case, .. }
+            | Engine::Exact { case, .. }
+            | Engine::Regex { case, .. } => *case,
+        }
+    }
+}
+
+/// Finds the first occurrence of query in value
+fn exact(value: &str, query: &str, case: Case, anchor: Anchor) -> Option<(i64, Vec<usize>)> {
+    let sensitive = case.is_sensitive(query);
+    let value: Vec<char> = value.chars().collect();
+    let query: Vec<char> = query.chars().collect();
+    if query.is_empty() || query.len() > value.len() {
+        return None;
+    }
+    let last = value.len() - query.len();
+    let candidates = match anchor {
+        Anchor::None => 0..=last,
+        Anchor::Start => 0..=0,
+        Anchor::End => last..=last,
+        Anchor::Both if last == 0 => 0..=0,
+        Anchor::Both => return None,
+    };
+    let start = candidates.into_iter().find(|start| {
+        value[*start..*start + query.len()]
+            .iter()
+            .zip(query.iter())
+            .all(|(a, b)| eq(*a, *b, sensitive))
+    })?;
+    Some((
+        positional_score(start, query.len()),
+        (start..start + query.len()).collect(),
+    ))
+}
+
+fn eq(a: char, b: char, sensitive: bool) -> bool {
+    if sensitive {
+        a == b
+    } else {
+        a == b || a.to_lowercase().eq(b.to_lowercase())
+    }
+}
+
+/// Score of exact, prefix and regex matches: longer and earlier matches are better
+fn positional_score(start: usize, len: usize) -> i64 {
+    len as i64 * MATCHED_CHAR_SCORE + (START_SCORE - start as i64).max(0)
+}
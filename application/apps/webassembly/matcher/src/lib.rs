@@ -2,16 +2,48 @@ extern crate fuzzy_matcher;
 extern crate serde_json;
 extern crate wasm_bindgen;
 
+mod engine;
+mod query;
+
+use engine::{Engine, Options};
 use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
-use std::{collections::HashMap, str::from_utf8};
+use query::Query;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_test::*;
 
 #[wasm_bindgen]
 pub struct Matcher {
-    matcher: SkimMatcherV2,
+    engine: Engine,
+    /// Fields of items as strings; used for matching
     items_initial: Vec<HashMap<String, String>>,
-    items_scored: HashMap<usize, (HashMap<String, String>, i64)>,
+    /// Fields of items as they were added
+    items_values: Vec<Map<String, Value>>,
+    items_scored: Vec<(HashMap<String, String>, i64)>,
+    /// Matched ranges of fields of items of the last search
+    items_ranges: Vec<HashMap<String, Vec<(usize, usize)>>>,
+    /// Indexes of matched items of the last search; not sorted
+    matched: Vec<usize>,
+    /// Indexes of matched items sorted by score (best first)
+    ranked: Vec<usize>,
+    /// Query and tag of the last search
+    query: Query,
+    tag: Option<String>,
+    /// false if results of the last search cannot be reused by the next
+    /// search (for example weights are changed)
+    reusable: bool,
+    min_score: Option<i64>,
+    max_results: Option<usize>,
+    /// Multipliers of scores of fields; fields without weight have weight 1
+    weights: HashMap<String, i64>,
+    /// Name of field with id of item (see `Options::id`)
+    id_field: Option<String>,
+    /// Indexes of items by ids
+    ids: HashMap<String, usize>,
+    /// Names of fields of all added items; used to recognize `field:` operator
+    keys: HashSet<String>,
 }
 
 impl Default for Matcher {
@@ -24,36 +56,140 @@ impl Default for Matcher {
 impl Matcher {
     #[wasm_bindgen]
     pub fn new() -> Self {
+        Self::with_engine(Engine::default())
+    }
+
+    /// Creates matcher with options as JSON object, e.g.
+    /// `{"algorithm": "prefix", "case": "ignore"}`. Algorithms: "skim" (default),
+    /// "clangd", "exact", "prefix", "regex". Case modes: "smart" (default),
+    /// "ignore", "respect".
+    #[wasm_bindgen]
+    pub fn with_options(options: String) -> Result<Matcher, String> {
+        match serde_json::from_str::<Options>(&options) {
+            Ok(options) => {
+                let id_field = options.id.clone();
+                let mut matcher = Self::with_engine(Engine::new(options));
+                matcher.id_field = id_field;
+                Ok(matcher)
+            }
+            Err(err) => Err(format!("Parsing options from JSON String failed: {}", err)),
+        }
+    }
+
+    fn with_engine(engine: Engine) -> Self {
         Self {
-            matcher: SkimMatcherV2::default(),
+            engine,
             items_initial: Vec::new(),
-            items_scored: HashMap::new(),
+            items_values: Vec::new(),
+            items_scored: Vec::new(),
+            items_ranges: Vec::new(),
+            matched: Vec::new(),
+            ranked: Vec::new(),
+            query: Query::default(),
+            tag: None,
+            reusable: true,
+            min_score: None,
+            max_results: None,
+            weights: HashMap::new(),
+            id_field: None,
+            ids: HashMap::new(),
+            keys: HashSet::new(),
         }
     }
 
+    /// Adds item as JSON object and scores it with the query of the last
+    /// search. Other items aren't rescored. Returns index of item.
     #[wasm_bindgen]
     pub fn set_item(&mut self, item: String) -> Result<usize, String> {
         match serde_json::from_str(&item) {
-            Ok::<HashMap<String, String>, _>(item) => {
-                self.items_initial.push(item);
-                self.search(String::new(), None);
-                Ok(self.items_initial.len() - 1)
-            }
+            Ok::<Map<String, Value>, _>(item) => self.push(vec![item]),
             Err(err) => Err(format!("Parsing item into JSON String failed: {}", err)),
         }
     }
 
+    /// Same as `set_item`, but for JS array of objects. Values of fields which
+    /// aren't strings are matched as JSON strings and returned as they are by
+    /// `get_value_of`. Returns index of the first added item.
     #[wasm_bindgen]
-    pub fn set_items(&mut self, items: String) -> Result<usize, String> {
-        match serde_json::from_str(&items) {
-            Ok::<Vec<HashMap<String, String>>, _>(mut items) => {
-                let from = self.items_initial.len();
-                self.items_initial.append(&mut items);
-                self.search(String::new(), None);
-                Ok(from)
-            }
-            Err(err) => Err(format!("Parsing item into JSON String failed: {}", err)),
+    pub fn set_items(&mut self, items: JsValue) -> Result<usize, String> {
+        match serde_wasm_bindgen::from_value(items) {
+            Ok::<Vec<Map<String, Value>>, _>(items) => self.push(items),
+            Err(err) => Err(format!("Parsing items from JS array failed: {}", err)),
+        }
+    }
+
+    /// Removes item. Indexes of the following items are shifted by one.
+    /// Returns false if item doesn't exist.
+    #[wasm_bindgen]
+    pub fn remove_item(&mut self, index: usize) -> bool {
+        if index >= self.items_initial.len() {
+            return false;
         }
+        self.items_initial.remove(index);
+        self.items_values.remove(index);
+        self.items_scored.remove(index);
+        self.items_ranges.remove(index);
+        let shift = |indexes: &mut Vec<usize>| {
+            indexes.retain(|i| *i != index);
+            indexes
+                .iter_mut()
+                .filter(|i| **i > index)
+                .for_each(|i| *i -= 1);
+        };
+        shift(&mut self.matched);
+        self.ids.retain(|_, i| *i != index);
+        self.ids
+            .values_mut()
+            .filter(|i| **i > index)
+            .for_each(|i| *i -= 1);
+        // Field of removed item can be absent in other items
+        self.keys = self
+            .items_initial
+            .iter()
+            .flat_map(|item| item.keys().cloned())
+            .collect();
+        self.rank();
+        true
+    }
+
+    /// Removes all items. Settings (weights, cutoff, limit) are kept.
+    #[wasm_bindgen]
+    pub fn clear(&mut self) {
+        self.items_initial.clear();
+        self.items_values.clear();
+        self.keys.clear();
+        self.ids.clear();
+        self.items_scored.clear();
+        self.items_ranges.clear();
+        self.matched.clear();
+        self.ranked.clear();
+    }
+
+    /// Returns current index of item with given id (see `Options::id`)
+    #[wasm_bindgen]
+    pub fn index_of(&self, id: String) -> Option<usize> {
+        self.ids.get(&id).copied()
+    }
+
+    /// Returns id of item (see `Options::id`)
+    #[wasm_bindgen]
+    pub fn id_of(&self, index: usize) -> Option<String> {
+        let field = self.id_field.as_ref()?;
+        self.items_initial.get(index)?.get(field).cloned()
+    }
+
+    /// Returns value of field of item as it was added
+    #[wasm_bindgen]
+    pub fn get_value_of(&self, index: usize, property: String) -> JsValue {
+        self.items_values
+            .get(index)
+            .and_then(|item| item.get(&property))
+            .and_then(|value| {
+                value
+                    .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+                    .ok()
+            })
+            .unwrap_or(JsValue::UNDEFINED)
     }
 
     #[wasm_bindgen]
@@ -66,42 +202,102 @@ impl Matcher {
         self.items_initial.is_empty()
     }
 
+    /// Scores items with query. Query consists of space separated terms; item is
+    /// matched if all terms are matched. Operators of terms (fzf-like):
+    /// - `'term` - exact match;
+    /// - `^term`, `term$` - value starts/ends with term (exact match);
+    /// - `!term` - item doesn't match term (exact match);
+    /// - `field:term` - term is matched with the given field only.
+    /// Operators aren't supported with "regex" algorithm; whole query is taken
+    /// as regular expression.
+    ///
+    /// If query narrows the query of the last search (for example extends the
+    /// last term or adds a new one) and tag is the same, only items matched by
+    /// the last search are rescored.
     #[wasm_bindgen]
     pub fn search(&mut self, query: String, tag: Option<String>) {
-        self.items_scored = HashMap::new();
-        let mut total_score: i64;
-        let mut temp_hashmap: HashMap<String, String> = HashMap::new();
-        for (index, item) in self.items_initial.iter().enumerate() {
-            total_score = 0;
-            for (key, value) in item {
-                if query.is_empty() {
-                    temp_hashmap.insert(key.clone(), value.clone());
-                    temp_hashmap.insert(format!("html_{}", key), value.to_string());
-                    total_score += (self.items_initial.len() - index) as i64;
-                } else {
-                    match self.matcher.fuzzy_indices(value.as_str(), &query) {
-                        Some(score) => {
-                            let tagged_match = self.tag_match(value.to_owned(), score.1, &tag);
-                            temp_hashmap.insert(key.clone(), value.to_owned());
-                            temp_hashmap.insert(format!("html_{}", key), tagged_match);
-                            total_score += score.0;
-                        }
-                        None => {
-                            temp_hashmap.insert(key.clone(), value.clone());
-                            temp_hashmap.insert(format!("html_{}", key), value.to_string());
-                        }
-                    }
-                }
+        let parsed = if self.engine.has_operators() {
+            Query::parse(&query, &|name| self.keys.contains(name))
+        } else {
+            Query::verbatim(&query)
+        };
+        let incremental = self.reusable
+            && self.engine.is_incremental()
+            && parsed.narrows(&self.query)
+            && tag == self.tag;
+        let candidates: Vec<usize> = if incremental {
+            std::mem::take(&mut self.matched)
+        } else {
+            self.matched.clear();
+            (0..self.items_initial.len()).collect()
+        };
+        self.engine.prepare(&query);
+        self.query = parsed;
+        self.tag = tag;
+        self.reusable = true;
+        for index in candidates {
+            if self.score(index) {
+                self.matched.push(index);
+            }
+        }
+        self.rank();
+    }
+
+    /// Sets min score of item to be included into ranked results. Applied
+    /// to search with not empty query only. None - no cutoff
+    #[wasm_bindgen]
+    pub fn set_min_score(&mut self, min_score: Option<i64>) {
+        self.min_score = min_score;
+        self.rank();
+    }
+
+    /// Sets max number of ranked results. None - no limit
+    #[wasm_bindgen]
+    pub fn set_max_results(&mut self, max_results: Option<usize>) {
+        self.max_results = max_results;
+        self.rank();
+    }
+
+    /// Sets weights of fields as JSON object, e.g. `{"name": 3, "path": 1}`. Score
+    /// of match in the field is multiplied by its weight; fields without weight
+    /// have weight 1. Fields with weight 0 are excluded from matching, but still
+    /// available (not highlighted) with `get_html_of`. Applied on next search.
+    #[wasm_bindgen]
+    pub fn set_weights(&mut self, weights: String) -> Result<(), String> {
+        match serde_json::from_str(&weights) {
+            Ok::<HashMap<String, u32>, _>(weights) => {
+                self.weights = weights
+                    .into_iter()
+                    .map(|(key, weight)| (key, weight as i64))
+                    .collect();
+                self.reusable = false;
+                Ok(())
             }
-            self.items_scored
-                .insert(index, (temp_hashmap.to_owned(), total_score));
-            temp_hashmap.clear();
+            Err(err) => Err(format!("Parsing weights from JSON String failed: {}", err)),
         }
     }
 
+    /// Returns indexes of matched items sorted by score (best first), starting
+    /// from `offset`. Items without any match aren't ranked.
+    #[wasm_bindgen]
+    pub fn get_ranked(&self, offset: usize, limit: Option<usize>) -> Vec<usize> {
+        self.ranked
+            .iter()
+            .skip(offset)
+            .take(limit.unwrap_or(usize::MAX))
+            .copied()
+            .collect()
+    }
+
+    /// Number of ranked items of the last search
+    #[wasm_bindgen]
+    pub fn ranked_len(&self) -> usize {
+        self.ranked.len()
+    }
+
     #[wasm_bindgen]
     pub fn get_html_of(&self, index: usize, property: String) -> Option<String> {
-        match self.items_scored.get(&index) {
+        match self.items_scored.get(index) {
             Some(item) => item
                 .0
                 .get(&property)
@@ -111,60 +307,233 @@ impl Matcher {
         }
     }
 
+    /// Returns matched ranges of the field of item as JSON array of
+    /// `{"start": number, "end": number}` (end is exclusive). Offsets are in
+    /// UTF-16 code units, so can be used with `String.prototype.slice`. Returns
+    /// empty array if field has no matches.
+    #[wasm_bindgen]
+    pub fn get_ranges_of(&self, index: usize, property: String) -> Option<String> {
+        self.items_scored.get(index)?.0.get(&property)?;
+        let ranges: Vec<serde_json::Value> = self
+            .items_ranges
+            .get(index)
+            .and_then(|fields| fields.get(&property))
+            .map(|ranges| {
+                ranges
+                    .iter()
+                    .map(|(start, end)| serde_json::json!({ "start": start, "end": end }))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Some(serde_json::Value::Array(ranges).to_string())
+    }
+
     #[wasm_bindgen]
     pub fn get_score(&self, index: usize) -> i64 {
-        match self.items_scored.get(&index) {
+        if self.query.is_empty() {
+            // Items keep the order of adding
+            return match self.items_initial.get(index) {
+                Some(item) => ((self.items_initial.len() - index) * item.len()) as i64,
+                None => 0,
+            };
+        }
+        match self.items_scored.get(index) {
             Some(item) => item.1,
             None => 0,
         }
     }
 
-    fn tag_match(&self, mut value: String, indexes: Vec<usize>, tag: &Option<String>) -> String {
-        let tag = tag.to_owned().unwrap_or_else(|| "span".to_string());
-        let op_tag = format!("<{}>", tag);
-        let ed_tag = format!("</{}>", tag);
-        let value_clone = value.clone();
-        let value_bytes = value_clone.as_bytes();
-        let mut index = indexes.iter().rev();
-        let mut start = 0;
-        let mut prev: usize = 0;
+    fn weight(&self, key: &str) -> i64 {
+        self.weights.get(key).copied().unwrap_or(1)
+    }
 
-        if let Some(&curr) = index.next() {
-            start = curr;
-            prev = curr;
+    /// Adds items and scores them with the query of the last search. Returns
+    /// index of the first added item. Fails without adding anything if id of
+    /// some item isn't unique.
+    fn push(&mut self, items: Vec<Map<String, Value>>) -> Result<usize, String> {
+        let from = self.items_initial.len();
+        let items: Vec<(HashMap<String, String>, Map<String, Value>)> = items
+            .into_iter()
+            .map(|values| {
+                let fields = values
+                    .iter()
+                    .map(|(key, value)| (key.clone(), stringify(value)))
+                    .collect();
+                (fields, values)
+            })
+            .collect();
+        if let Some(field) = self.id_field.as_ref() {
+            let mut ids: HashMap<String, usize> = HashMap::new();
+            for (i, (fields, _)) in items.iter().enumerate() {
+                if let Some(id) = fields.get(field) {
+                    if self.ids.contains_key(id) || ids.insert(id.clone(), from + i).is_some() {
+                        return Err(format!("Item with id \"{}\" already exists", id));
+                    }
+                }
+            }
+            self.ids.extend(ids);
         }
+        for (item, values) in items {
+            let index = self.items_initial.len();
+            self.keys.extend(item.keys().cloned());
+            self.items_initial.push(item);
+            self.items_values.push(values);
+            self.items_scored.push((HashMap::new(), 0));
+            self.items_ranges.push(HashMap::new());
+            if self.score(index) {
+                self.matched.push(index);
+                if self.query.is_empty()
+                    && self.max_results.is_none_or(|max| self.ranked.len() < max)
+                {
+                    // Ranked in the order of adding; no need to sort
+                    self.ranked.push(index);
+                }
+            }
+        }
+        if !self.query.is_empty() {
+            self.rank();
+        }
+        Ok(from)
+    }
 
-        for &curr in index {
-            if (curr + 1) != prev {
-                match from_utf8(&value_bytes[prev..start + 1]) {
-                    Ok(substring) => {
-                        value.replace_range(
-                            prev..start + 1,
-                            format!("{}{}{}", op_tag, substring, ed_tag).as_str(),
-                        );
-                        start = curr;
-                        prev = curr;
+    /// Scores item with the query of the last search; returns true if item is
+    /// matched. Highlights of all terms are combined.
+    fn score(&mut self, index: usize) -> bool {
+        let item = &self.items_initial[index];
+        let mut positions: HashMap<&str, Vec<usize>> = HashMap::new();
+        let mut total_score: i64 = 0;
+        let mut matched = true;
+        for term in &self.query.terms {
+            let mut term_matched = false;
+            for (key, value) in item {
+                let weight = self.weight(key);
+                if weight == 0 || term.field.as_ref().is_some_and(|field| field != key) {
+                    continue;
+                }
+                if let Some((score, indices)) = self.engine.term_indices(value, term) {
+                    term_matched = true;
+                    if term.negated {
+                        break;
                     }
-                    Err(_err) => return value,
+                    total_score += score * weight;
+                    positions.entry(key).or_default().extend(indices);
                 }
-            } else {
-                prev = curr;
+            }
+            if term_matched == term.negated {
+                matched = false;
+                break;
             }
         }
-        match from_utf8(&value_bytes[prev..start + 1]) {
-            Ok(substring) => {
-                value.replace_range(
-                    prev..start + 1,
-                    format!("{}{}{}", op_tag, substring, ed_tag).as_str(),
-                );
-                value
+        if !matched {
+            positions.clear();
+            total_score = 0;
+        }
+        let mut fields: HashMap<String, String> = HashMap::new();
+        let mut ranges: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+        for (key, value) in item {
+            fields.insert(key.clone(), value.clone());
+            match positions.get_mut(key.as_str()) {
+                Some(indices) => {
+                    indices.sort_unstable();
+                    indices.dedup();
+                    let (tagged_match, matched_ranges) = self.tag_match(value, indices, &self.tag);
+                    fields.insert(format!("html_{}", key), tagged_match);
+                    ranges.insert(key.clone(), matched_ranges);
+                }
+                None => {
+                    fields.insert(format!("html_{}", key), escape(value));
+                }
+            }
+        }
+        self.items_scored[index] = (fields, total_score);
+        self.items_ranges[index] = ranges;
+        matched
+    }
+
+    fn rank(&mut self) {
+        let limit = self.max_results.unwrap_or(usize::MAX);
+        if self.query.is_empty() {
+            // With empty query all items are matched and keep the order of adding
+            self.ranked = self.matched.iter().take(limit).copied().collect();
+            return;
+        }
+        let mut ranked: Vec<(usize, i64)> = self
+            .matched
+            .iter()
+            .map(|index| (*index, self.get_score(*index)))
+            .filter(|(_, score)| self.min_score.is_none_or(|min| *score >= min))
+            .collect();
+        ranked.sort_by(|(a, a_score), (b, b_score)| b_score.cmp(a_score).then(a.cmp(b)));
+        ranked.truncate(limit);
+        self.ranked = ranked.into_iter().map(|(index, _)| index).collect();
+    }
+
+    /// Wraps matched chars (`indexes` are positions of chars, not bytes) into
+    /// `tag` and escapes the rest. Returns HTML and matched ranges in UTF-16
+    /// code units.
+    fn tag_match(
+        &self,
+        value: &str,
+        indexes: &[usize],
+        tag: &Option<String>,
+    ) -> (String, Vec<(usize, usize)>) {
+        let tag = tag.as_deref().unwrap_or("span");
+        let mut html = String::with_capacity(value.len());
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+        let mut indexes = indexes.iter().peekable();
+        let mut offset = 0;
+        let mut open = false;
+        for (pos, ch) in value.chars().enumerate() {
+            while indexes.next_if(|&&index| index < pos).is_some() {}
+            let matched = indexes.next_if(|&&index| index == pos).is_some();
+            if matched && !open {
+                html.push_str(&format!("<{}>", tag));
+                ranges.push((offset, offset));
+            } else if !matched && open {
+                html.push_str(&format!("</{}>", tag));
+            }
+            open = matched;
+            escape_char(ch, &mut html);
+            offset += ch.len_utf16();
+            if let (true, Some(range)) = (matched, ranges.last_mut()) {
+                range.1 = offset;
             }
-            Err(_err) => value,
         }
+        if open {
+            html.push_str(&format!("</{}>", tag));
+        }
+        (html, ranges)
     }
 }
 
-fn test(query: String, tag: Option<String>, expected: Vec<HashMap<&str, &str>>) {
+fn escape_char(ch: char, html: &mut String) {
+    match ch {
+        '&' => html.push_str("&amp;"),
+        '<' => html.push_str("&lt;"),
+        '>' => html.push_str("&gt;"),
+        '"' => html.push_str("&quot;"),
+        '\'' => html.push_str("&#39;"),
+        _ => html.push(ch),
+    }
+}
+
+/// Value of field used for matching. Strings are taken as they are; other
+/// values are converted into JSON strings.
+fn stringify(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+fn escape(value: &str) -> String {
+    let mut html = String::with_capacity(value.len());
+    value.chars().for_each(|ch| escape_char(ch, &mut html));
+    html
+}
+
+fn matcher() -> Matcher {
     let mut matcher = Matcher::new();
     let items = Vec::from([
         HashMap::from([
@@ -193,6 +562,11 @@ fn test(query: String, tag: Option<String>, expected: Vec<HashMap<&str, &str>>)
             Err(err) => panic!("{}", err),
         }
     }
+    matcher
+}
+
+fn test(query: String, tag: Option<String>, expected: Vec<HashMap<&str, &str>>) {
+    let mut matcher = matcher();
     matcher.search(query, tag);
     for (index, map) in expected.iter().enumerate() {
         for (&key, &value) in map {
@@ -329,3 +703,359 @@ fn few_match() {
     ]);
     test(query, tag, expected);
 }
+
+#[wasm_bindgen_test]
+fn ranked() {
+    let mut matcher = matcher();
+    matcher.search("mel".to_string(), None);
+    assert_eq!(matcher.get_ranked(0, None), vec![1, 2, 0]);
+    assert_eq!(matcher.get_ranked(1, Some(1)), vec![2]);
+    assert!(matcher.get_ranked(3, None).is_empty());
+    matcher.search("g".to_string(), None);
+    assert_eq!(matcher.ranked_len(), 2);
+    assert_eq!(matcher.get_ranked(0, None), vec![0, 2]);
+    matcher.search(String::new(), None);
+    assert_eq!(matcher.get_ranked(0, None), vec![0, 1, 2]);
+}
+
+#[wasm_bindgen_test]
+fn ranked_with_limits() {
+    let mut matcher = matcher();
+    matcher.search("mel".to_string(), None);
+    matcher.set_min_score(Some(matcher.get_score(2)));
+    assert_eq!(matcher.get_ranked(0, None), vec![1, 2]);
+    matcher.set_max_results(Some(1));
+    assert_eq!(matcher.get_ranked(0, None), vec![1]);
+    matcher.set_min_score(None);
+    matcher.set_max_results(None);
+    assert_eq!(matcher.ranked_len(), 3);
+    // Cutoff isn't applied to empty query
+    matcher.set_min_score(Some(i64::MAX));
+    matcher.search(String::new(), None);
+    assert_eq!(matcher.ranked_len(), 3);
+}
+
+#[wasm_bindgen_test]
+fn weighted() {
+    let mut matcher = matcher();
+    let name = SkimMatcherV2::default()
+        .fuzzy_match("small_file.log", "g")
+        .unwrap();
+    // Size of first item is excluded, so only its name is matched
+    assert!(matcher
+        .set_weights(r#"{"name": 3, "path": 0, "size": 0}"#.to_string())
+        .is_ok());
+    matcher.search("g".to_string(), None);
+    assert_eq!(matcher.get_score(2), name * 3);
+    assert_eq!(
+        matcher.get_html_of(0, "html_size".to_string()),
+        Some("20gb".to_string())
+    );
+    assert_eq!(matcher.get_ranked(0, None).len(), 2);
+    assert!(matcher.set_weights(r#"{"name": -1}"#.to_string()).is_err());
+}
+
+#[wasm_bindgen_test]
+fn escaped_match() {
+    let mut matcher = Matcher::new();
+    assert!(matcher
+        .set_item(r#"{"name": "<script>a&b</script>", "size": "1\"kb"}"#.to_string())
+        .is_ok());
+    assert_eq!(
+        matcher.get_html_of(0, "html_size".to_string()),
+        Some("1&quot;kb".to_string())
+    );
+    matcher.search("ab".to_string(), None);
+    assert_eq!(
+        matcher.get_html_of(0, "html_name".to_string()),
+        Some("&lt;script&gt;<span>a</span>&amp;<span>b</span>&lt;/script&gt;".to_string())
+    );
+}
+
+#[wasm_bindgen_test]
+fn unicode_match() {
+    let mut matcher = Matcher::new();
+    assert!(matcher
+        .set_item(r#"{"name": "übung_😀_файл.log"}"#.to_string())
+        .is_ok());
+    matcher.search("файл".to_string(), None);
+    assert_eq!(
+        matcher.get_html_of(0, "html_name".to_string()),
+        Some("übung_😀_<span>файл</span>.log".to_string())
+    );
+    // Emoji takes 2 UTF-16 code units
+    assert_eq!(
+        matcher.get_ranges_of(0, "name".to_string()),
+        Some(r#"[{"end":13,"start":9}]"#.to_string())
+    );
+    matcher.search("bung".to_string(), None);
+    assert_eq!(
+        matcher.get_ranges_of(0, "name".to_string()),
+        Some(r#"[{"end":5,"start":1}]"#.to_string())
+    );
+    assert_eq!(matcher.get_ranges_of(0, "size".to_string()), None);
+}
+
+#[wasm_bindgen_test]
+fn incremental_search() {
+    let mut matcher = matcher();
+    matcher.search("l".to_string(), None);
+    assert_eq!(matcher.ranked_len(), 3);
+    matcher.search("lo".to_string(), None);
+    assert_eq!(matcher.get_ranked(0, None), vec![2]);
+    // Items added during search are scored with the current query
+    assert!(matcher
+        .set_item(r#"{"name": "lot.txt"}"#.to_string())
+        .is_ok());
+    assert_eq!(matcher.ranked_len(), 2);
+    assert_eq!(
+        matcher.get_html_of(3, "html_name".to_string()),
+        Some("<span>lo</span>t.txt".to_string())
+    );
+    // Extended query: not matched items stay not matched
+    matcher.search("log".to_string(), None);
+    assert_eq!(matcher.get_ranked(0, None), vec![2]);
+    assert_eq!(
+        matcher.get_html_of(0, "html_name".to_string()),
+        Some("very_large_file.dlt".to_string())
+    );
+    assert_eq!(matcher.get_score(0), 0);
+    // Not extended query rescans all items
+    matcher.search("lar".to_string(), None);
+    assert_eq!(matcher.get_ranked(0, None), vec![0]);
+}
+
+#[wasm_bindgen_test]
+fn remove_and_clear() {
+    let mut matcher = matcher();
+    matcher.search("g".to_string(), None);
+    assert_eq!(matcher.get_ranked(0, None), vec![0, 2]);
+    assert!(matcher.remove_item(0));
+    assert!(!matcher.remove_item(2));
+    assert_eq!(matcher.len(), 2);
+    assert_eq!(matcher.get_ranked(0, None), vec![1]);
+    assert_eq!(
+        matcher.get_html_of(1, "html_name".to_string()),
+        Some("small_file.lo<span>g</span>".to_string())
+    );
+    matcher.clear();
+    assert!(matcher.is_empty());
+    assert_eq!(matcher.ranked_len(), 0);
+    assert!(matcher
+        .set_item(r#"{"name": "grep.log"}"#.to_string())
+        .is_ok());
+    assert_eq!(matcher.get_ranked(0, None), vec![0]);
+}
+
+/// Returns indexes of matched items in the order of adding
+fn matched_with(options: &str, query: &str) -> Vec<usize> {
+    let mut matcher = match Matcher::with_options(options.to_string()) {
+        Ok(matcher) => matcher,
+        Err(err) => panic!("{}", err),
+    };
+    for name in ["Readme.md", "read_me.txt", "thread.rs"] {
+        assert!(matcher
+            .set_item(format!(r#"{{"name": "{}"}}"#, name))
+            .is_ok());
+    }
+    matcher.search(query.to_string(), None);
+    let mut matched = matcher.get_ranked(0, None);
+    matched.sort();
+    matched
+}
+
+#[wasm_bindgen_test]
+fn algorithms() {
+    assert_eq!(matched_with(r#"{"algorithm": "skim"}"#, "rdme"), vec![0, 1]);
+    assert_eq!(
+        matched_with(r#"{"algorithm": "clangd"}"#, "rdme"),
+        vec![0, 1]
+    );
+    assert_eq!(
+        matched_with(r#"{"algorithm": "exact"}"#, "read"),
+        vec![0, 1, 2]
+    );
+    assert_eq!(
+        matched_with(r#"{"algorithm": "prefix"}"#, "read"),
+        vec![0, 1]
+    );
+    assert_eq!(
+        matched_with(r#"{"algorithm": "regex"}"#, r"\.(md|rs)$"),
+        vec![0, 2]
+    );
+    // Invalid regex doesn't match anything
+    assert!(matched_with(r#"{"algorithm": "regex"}"#, "(").is_empty());
+    assert!(Matcher::with_options(r#"{"algorithm": "unknown"}"#.to_string()).is_err());
+    assert!(Matcher::with_options(r#"{"mode": "exact"}"#.to_string()).is_err());
+}
+
+#[wasm_bindgen_test]
+fn case_modes() {
+    assert_eq!(matched_with(r#"{"algorithm": "prefix"}"#, "Read"), vec![0]);
+    assert_eq!(
+        matched_with(r#"{"algorithm": "prefix", "case": "ignore"}"#, "READ"),
+        vec![0, 1]
+    );
+    assert_eq!(
+        matched_with(r#"{"algorithm": "exact", "case": "respect"}"#, "read"),
+        vec![1, 2]
+    );
+    assert_eq!(matched_with(r#"{"case": "respect"}"#, "rdme"), vec![1]);
+    assert_eq!(matched_with(r#"{"algorithm": "regex"}"#, "^R"), vec![0]);
+}
+
+#[wasm_bindgen_test]
+fn not_string_values() {
+    let mut matcher = Matcher::new();
+    assert!(matcher
+        .set_item(
+            r#"{"name": "core.dlt", "size": 2048, "tags": ["trace"], "note": null}"#.to_string()
+        )
+        .is_ok());
+    matcher.search("204".to_string(), None);
+    assert_eq!(
+        matcher.get_html_of(0, "html_size".to_string()),
+        Some("<span>204</span>8".to_string())
+    );
+    matcher.search("trace".to_string(), None);
+    assert_eq!(
+        matcher.get_html_of(0, "html_tags".to_string()),
+        Some("[&quot;<span>trace</span>&quot;]".to_string())
+    );
+    assert_eq!(
+        matcher.get_html_of(0, "note".to_string()),
+        Some(String::new())
+    );
+}
+
+#[wasm_bindgen_test]
+fn stable_ids() {
+    let mut matcher = match Matcher::with_options(r#"{"id": "path"}"#.to_string()) {
+        Ok(matcher) => matcher,
+        Err(err) => panic!("{}", err),
+    };
+    for path in ["/a.log", "/b.log", "/c.log"] {
+        assert!(matcher
+            .set_item(format!(r#"{{"path": "{}"}}"#, path))
+            .is_ok());
+    }
+    assert!(matcher
+        .set_item(r#"{"path": "/b.log"}"#.to_string())
+        .is_err());
+    assert_eq!(matcher.len(), 3);
+    assert_eq!(matcher.index_of("/c.log".to_string()), Some(2));
+    assert!(matcher.remove_item(1));
+    assert_eq!(matcher.index_of("/b.log".to_string()), None);
+    assert_eq!(matcher.index_of("/c.log".to_string()), Some(1));
+    assert_eq!(matcher.id_of(1), Some("/c.log".to_string()));
+    matcher.clear();
+    assert_eq!(matcher.index_of("/a.log".to_string()), None);
+}
+
+fn files() -> Matcher {
+    let mut matcher = Matcher::new();
+    for (name, path) in [
+        ("trace.dlt", "/home/user/trace.dlt"),
+        ("trace.dlt.tmp", "/tmp/trace.dlt.tmp"),
+        ("system.log", "/home/user/system.log"),
+        ("dlt_viewer.txt", "/opt/dlt_viewer.txt"),
+    ] {
+        assert!(matcher
+            .set_item(format!(r#"{{"name": "{}", "path": "{}"}}"#, name, path))
+            .is_ok());
+    }
+    matcher
+}
+
+fn ranked_by(matcher: &mut Matcher, query: &str) -> Vec<usize> {
+    matcher.search(query.to_string(), None);
+    let mut matched = matcher.get_ranked(0, None);
+    matched.sort();
+    matched
+}
+
+#[wasm_bindgen_test]
+fn query_operators() {
+    let mut matcher = files();
+    assert_eq!(ranked_by(&mut matcher, "dlt"), vec![0, 1, 3]);
+    assert_eq!(ranked_by(&mut matcher, "dlt !tmp"), vec![0, 3]);
+    assert_eq!(ranked_by(&mut matcher, "dlt !tmp ^/home"), vec![0]);
+    assert_eq!(ranked_by(&mut matcher, "'.dlt"), vec![0, 1]);
+    assert_eq!(ranked_by(&mut matcher, ".dlt$"), vec![0]);
+    assert_eq!(ranked_by(&mut matcher, "^system.log$"), vec![2]);
+    assert_eq!(ranked_by(&mut matcher, "name:^dlt"), vec![3]);
+    assert_eq!(ranked_by(&mut matcher, "path:opt"), vec![3]);
+    assert_eq!(ranked_by(&mut matcher, "!name:dlt"), vec![2]);
+    // Unknown field: colon is a part of term
+    assert!(ranked_by(&mut matcher, "size:dlt").is_empty());
+    // Operators without text are taken as they are
+    assert!(ranked_by(&mut matcher, "dlt !").is_empty());
+}
+
+#[wasm_bindgen_test]
+fn keys_of_removed_items() {
+    let mut matcher = files();
+    assert!(matcher.set_item(r#"{"size": "dlt"}"#.to_string()).is_ok());
+    assert_eq!(ranked_by(&mut matcher, "size:dlt"), vec![4]);
+    assert!(matcher.remove_item(4));
+    assert!(matcher
+        .set_item(r#"{"name": "size:dlt"}"#.to_string())
+        .is_ok());
+    // Field "size" is gone with removed item: colon is a part of term
+    assert_eq!(ranked_by(&mut matcher, "size:dlt"), vec![4]);
+    assert_eq!(
+        matcher.get_html_of(4, "html_name".to_string()),
+        Some("<span>size:dlt</span>".to_string())
+    );
+}
+
+#[wasm_bindgen_test]
+fn combined_highlights() {
+    let mut matcher = files();
+    matcher.search("'trace dlt$".to_string(), None);
+    assert_eq!(
+        matcher.get_html_of(0, "html_name".to_string()),
+        Some("<span>trace</span>.<span>dlt</span>".to_string())
+    );
+    assert_eq!(
+        matcher.get_ranges_of(0, "name".to_string()),
+        Some(r#"[{"end":5,"start":0},{"end":9,"start":6}]"#.to_string())
+    );
+    // Negated terms aren't highlighted
+    matcher.search("'trace !tmp".to_string(), None);
+    assert_eq!(
+        matcher.get_html_of(0, "html_path".to_string()),
+        Some("/home/user/<span>trace</span>.dlt".to_string())
+    );
+    // Not matched items have no highlights even if some terms are matched
+    assert_eq!(
+        matcher.get_html_of(1, "html_name".to_string()),
+        Some("trace.dlt.tmp".to_string())
+    );
+}
+
+#[wasm_bindgen_test]
+fn incremental_terms() {
+    let mut matcher = files();
+    assert_eq!(ranked_by(&mut matcher, "dl"), vec![0, 1, 3]);
+    // All items have "t" somewhere
+    assert!(ranked_by(&mut matcher, "dlt !t").is_empty());
+    // Extended negated term excludes less items, so all items are rescanned
+    assert_eq!(ranked_by(&mut matcher, "dlt !tmp"), vec![0, 3]);
+    assert_eq!(ranked_by(&mut matcher, "dlt !tmp ^/"), vec![0, 3]);
+    assert_eq!(ranked_by(&mut matcher, "dlt !tmp ^/o"), vec![3]);
+    assert_eq!(ranked_by(&mut matcher, "dlt"), vec![0, 1, 3]);
+}
+
+#[wasm_bindgen_test]
+fn regex_query_with_spaces() {
+    let mut matcher = match Matcher::with_options(r#"{"algorithm": "regex"}"#.to_string()) {
+        Ok(matcher) => matcher,
+        Err(err) => panic!("{}", err),
+    };
+    assert!(matcher
+        .set_item(r#"{"name": "core dump.log"}"#.to_string())
+        .is_ok());
+    assert_eq!(ranked_by(&mut matcher, "e d"), vec![0]);
+    assert!(ranked_by(&mut matcher, "!core").is_empty());
+}
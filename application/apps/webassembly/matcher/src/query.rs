@@ -0,0 +1,143 @@
+/// Position of exact match in value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Anchor {
+    /// Anywhere in value: `'term`
+    None,
+    /// Value starts with term: `^term`
+    Start,
+    /// Value ends with term: `term$`
+    End,
+    /// Value equals to term: `^term$`
+    Both,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// Matched with the algorithm of matcher
+    Fuzzy,
+    Exact(Anchor),
+}
+
+/// Space separated part of query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Term {
+    pub text: String,
+    pub kind: Kind,
+    /// Item is matched only if none of its fields matches term: `!term`. Negated
+    /// terms are always exact.
+    pub negated: bool,
+    /// Term is matched with the given field only: `field:term`
+    pub field: Option<String>,
+}
+
+impl Term {
+    /// Parses operators of term in the order `!`, `field:`, `'` or `^`, `$`. If
+    /// nothing is left after operators, the token is taken as a fuzzy term.
+    fn parse(token: &str, is_field: &dyn Fn(&str) -> bool) -> Term {
+        let mut text = token;
+        let negated = text.len() > 1 && text.starts_with('!');
+        if negated {
+            text = &text[1..];
+        }
+        let mut field = None;
+        if let Some((name, rest)) = text.split_once(':') {
+            if !rest.is_empty() && is_field(name) {
+                field = Some(name.to_owned());
+                text = rest;
+            }
+        }
+        let mut anchor = None;
+        if text.len() > 1 && text.starts_with('\'') {
+            text = &text[1..];
+            anchor = Some(Anchor::None);
+        } else if text.len() > 1 && text.starts_with('^') {
+            text = &text[1..];
+            anchor = Some(Anchor::Start);
+        }
+        if text.len() > 1 && text.ends_with('$') && anchor != Some(Anchor::None) {
+            text = &text[..text.len() - 1];
+            anchor = Some(match anchor {
+                Some(Anchor::Start) => Anchor::Both,
+                _ => Anchor::End,
+            });
+        }
+        let kind = match anchor {
+            Some(anchor) => Kind::Exact(anchor),
+            None if negated => Kind::Exact(Anchor::None),
+            None => Kind::Fuzzy,
+        };
+        Term {
+            text: text.to_owned(),
+            kind,
+            negated,
+            field,
+        }
+    }
+
+    /// true if all items matched by this term are matched by `prev` as well
+    fn narrows(&self, prev: &Term) -> bool {
+        if self.negated || prev.negated || self.field != prev.field {
+            return self == prev;
+        }
+        match (self.kind, prev.kind) {
+            (Kind::Fuzzy, Kind::Fuzzy) => self.text.starts_with(&prev.text),
+            (Kind::Exact(Anchor::None | Anchor::Start), Kind::Exact(Anchor::None))
+            | (Kind::Exact(Anchor::Start), Kind::Exact(Anchor::Start)) => {
+                self.text.starts_with(&prev.text)
+            }
+            _ => self == prev,
+        }
+    }
+}
+
+/// Parsed search query: all terms should be matched
+#[derive(Debug, Clone, Default)]
+pub struct Query {
+    pub terms: Vec<Term>,
+}
+
+impl Query {
+    /// Splits query into terms by whitespaces. `is_field` tells whether a prefix
+    /// before `:` is a field of items; otherwise `:` is a part of term.
+    pub fn parse(query: &str, is_field: &dyn Fn(&str) -> bool) -> Self {
+        Query {
+            terms: query
+                .split_whitespace()
+                .map(|token| Term::parse(token, is_field))
+                .collect(),
+        }
+    }
+
+    /// Takes whole query as a single fuzzy term (used if operators aren't
+    /// supported by algorithm)
+    pub fn verbatim(query: &str) -> Self {
+        Query {
+            terms: if query.is_empty() {
+                Vec::new()
+            } else {
+                vec![Term {
+                    text: query.to_owned(),
+                    kind: Kind::Fuzzy,
+                    negated: false,
+                    field: None,
+                }]
+            },
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// true if all items matched by this query are matched by `prev` as well,
+    /// so only matches of `prev` should be rescored
+    pub fn narrows(&self, prev: &Query) -> bool {
+        !prev.is_empty()
+            && self.terms.len() >= prev.terms.len()
+            && self
+                .terms
+                .iter()
+                .zip(prev.terms.iter())
+                .all(|(term, prev)| term.narrows(prev))
+    }
+}
@@ -48,4 +48,51 @@ describe('test', function () {
         expect(result).toBeUndefined();
         done();
     });
+
+    it('lint filter', (done) => {
+        const { lint_filter } = wasm;
+        const invalid = JSON.parse(lint_filter('\\[Warn(\\]', false, false, true));
+        expect(invalid.length).toEqual(1);
+        expect(invalid[0].severity).toEqual('error');
+        expect(invalid[0].span).toEqual({ start: 6, end: 7 });
+        const suspicious = JSON.parse(lint_filter('.*error.*', false, false, true));
+        expect(suspicious.map((d: { code: string }) => d.code)).toEqual([
+            'redundant-wildcard',
+            'redundant-wildcard',
+        ]);
+        expect(JSON.parse(lint_filter('error', false, true, false))).toEqual([]);
+        done();
+    });
+
+    it('preview filter', (done) => {
+        const { preview_filter } = wasm;
+        const previews = JSON.parse(
+            preview_filter('err', JSON.stringify({ is_word: true }), JSON.stringify(['err errors', 'ok'])),
+        );
+        expect(previews).toEqual([
+            { matched: true, ranges: [{ start: 0, end: 3 }] },
+            { matched: false, ranges: [] },
+        ]);
+        expect(() => preview_filter('(', JSON.stringify({ is_regex: true }), '[]')).toThrow();
+        done();
+    });
+
+    it('filter set', (done) => {
+        const { FilterSet } = wasm;
+        const filters = FilterSet.compile(
+            JSON.stringify([
+                { filter: 'error', flags: { ignore_case: true }, color: '#ff0000' },
+                { filter: '\\d+ms', flags: { is_regex: true } },
+            ]),
+        );
+        expect(filters.len()).toEqual(2);
+        expect(JSON.parse(filters.match_line('ERROR in 10ms'))).toEqual([
+            { index: 0, color: '#ff0000', ranges: [{ start: 0, end: 5 }] },
+            { index: 1, color: null, ranges: [{ start: 9, end: 13 }] },
+        ]);
+        expect(JSON.parse(filters.match_line('ok'))).toEqual([]);
+        filters.free();
+        expect(() => FilterSet.compile(JSON.stringify([{ filter: '(', flags: { is_regex: true } }]))).toThrow();
+        done();
+    });
 });
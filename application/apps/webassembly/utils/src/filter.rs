@@ -5,6 +5,9 @@ use std::str::FromStr;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_test::*;
 
+/// Builds regular expression from filter; shared with backend
+pub use filters::filter_as_regex;
+
 #[wasm_bindgen]
 pub fn get_filter_error(
     filter: String,
@@ -16,18 +19,6 @@ pub fn get_filter_error(
     Regex::from_str(&regex_as_str).map_or_else(|err| Some(err.to_string()), |_| None)
 }
 
-fn filter_as_regex(filter: String, case_sensitive: bool, whole_word: bool, regex: bool) -> String {
-    let word_marker = if whole_word { "\\b" } else { "" };
-    let ignore_case_start = if case_sensitive { "(?i)" } else { "" };
-    let ignore_case_end = if case_sensitive { "(?-i)" } else { "" };
-    let subject = if regex {
-        filter
-    } else {
-        regex::escape(&filter)
-    };
-    format!("{ignore_case_start}{word_marker}{subject}{word_marker}{ignore_case_end}",)
-}
-
 #[wasm_bindgen_test]
 fn plain_string_regex_on() {
     let filter = String::from("Some random filter");
@@ -0,0 +1,236 @@
+use crate::{
+    filter::filter_as_regex,
+    lint::{span, Span},
+    preview::Flags,
+};
+use regex::{Regex, RegexSet};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+/// Filter of set, e.g. `{"filter": "error", "flags": {"ignore_case": true}, "color": "#ff0000"}`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SetFilter {
+    pub filter: String,
+    #[serde(default)]
+    pub flags: Flags,
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct FilterMatch {
+    /// Position of filter in set
+    pub index: usize,
+    pub color: Option<String>,
+    /// Not empty matches of filter in line; offsets are in UTF-16 code units
+    pub ranges: Vec<Span>,
+}
+
+/// Compiled set of filters. `RegexSet` tells in one pass which filters match a
+/// line, so ranges are searched only for matched filters.
+#[wasm_bindgen]
+pub struct FilterSet {
+    set: RegexSet,
+    regexes: Vec<Regex>,
+    colors: Vec<Option<String>>,
+}
+
+impl FilterSet {
+    pub fn new(filters: Vec<SetFilter>) -> Result<Self, String> {
+        let patterns: Vec<String> = filters
+            .iter()
+            .map(|filter| {
+                filter_as_regex(
+                    filter.filter.clone(),
+                    filter.flags.ignore_case,
+                    filter.flags.is_word,
+                    filter.flags.is_regex,
+                )
+            })
+            .collect();
+        let regexes = patterns
+            .iter()
+            .enumerate()
+            .map(|(index, pattern)| {
+                Regex::new(pattern).map_err(|err| format!("Filter #{} is invalid: {}", index, err))
+            })
+            .collect::<Result<Vec<Regex>, String>>()?;
+        let set = RegexSet::new(&patterns).map_err(|err| err.to_string())?;
+        Ok(FilterSet {
+            set,
+            regexes,
+            colors: filters.into_iter().map(|filter| filter.color).collect(),
+        })
+    }
+
+    /// Matched filters of line in the order of set. Filters, which match
+    /// empty strings only (e.g. `x*` or an empty filter), aren't included
+    pub fn matches(&self, line: &str) -> Vec<FilterMatch> {
+        self.set
+            .matches(line)
+            .into_iter()
+            .filter_map(|index| {
+                let ranges: Vec<Span> = self.regexes[index]
+                    .find_iter(line)
+                    .filter(|found| !found.is_empty())
+                    .map(|found| span(line, found.start(), found.end()))
+                    .collect();
+                (!ranges.is_empty()).then(|| FilterMatch {
+                    index,
+                    color: self.colors[index].clone(),
+                    ranges,
+                })
+            })
+            .collect()
+    }
+
+    /// Same rule as `matches`: empty matches are ignored
+    pub fn is_match(&self, line: &str) -> bool {
+        self.set.matches(line).into_iter().any(|index| {
+            self.regexes[index]
+                .find_iter(line)
+                .any(|found| !found.is_empty())
+        })
+    }
+}
+
+#[wasm_bindgen]
+impl FilterSet {
+    /// Compiles JSON array of filters (see `SetFilter`)
+    #[wasm_bindgen]
+    pub fn compile(filters: String) -> Result<FilterSet, String> {
+        match serde_json::from_str(&filters) {
+            Ok::<Vec<SetFilter>, _>(filters) => FilterSet::new(filters),
+            Err(err) => Err(format!("Parsing filters from JSON String failed: {}", err)),
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn len(&self) -> usize {
+        self.regexes.len()
+    }
+
+    #[wasm_bindgen]
+    pub fn is_empty(&self) -> bool {
+        self.regexes.is_empty()
+    }
+
+    /// Returns JSON array of matched filters of line (see `FilterMatch`)
+    #[wasm_bindgen]
+    pub fn match_line(&self, line: &str) -> String {
+        serde_json::to_string(&self.matches(line)).unwrap_or_else(|_| String::from("[]"))
+    }
+
+    /// Same as `match_line` for JSON array of lines; returns array of results
+    #[wasm_bindgen]
+    pub fn match_lines(&self, lines_json: String) -> Result<String, String> {
+        let lines: Vec<String> = serde_json::from_str(&lines_json)
+            .map_err(|err| format!("Parsing lines from JSON String failed: {}", err))?;
+        let matches: Vec<Vec<FilterMatch>> = lines.iter().map(|line| self.matches(line)).collect();
+        serde_json::to_string(&matches).map_err(|err| err.to_string())
+    }
+}
+
+fn filters() -> FilterSet {
+    match FilterSet::compile(String::from(
+        r##"[
+            {"filter": "error", "flags": {"ignore_case": true}, "color": "#ff0000"},
+            {"filter": "\\d+ms", "flags": {"is_regex": true}},
+            {"filter": "a.b", "color": "#00ff00"},
+            {"filter": "id", "flags": {"is_word": true}}
+        ]"##,
+    )) {
+        Ok(filters) => filters,
+        Err(err) => panic!("{}", err),
+    }
+}
+
+fn matched(filters: &FilterSet, line: &str) -> Vec<(usize, Vec<(usize, usize)>)> {
+    filters
+        .matches(line)
+        .into_iter()
+        .map(|found| {
+            (
+                found.index,
+                found
+                    .ranges
+                    .iter()
+                    .map(|range| (range.start, range.end))
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn matched_filters() {
+    let filters = filters();
+    assert_eq!(filters.len(), 4);
+    assert_eq!(
+        matched(&filters, "ERROR: id 10ms, error 5ms"),
+        vec![
+            (0, vec![(0, 5), (16, 21)]),
+            (1, vec![(10, 14), (22, 25)]),
+            (3, vec![(7, 9)])
+        ]
+    );
+    assert_eq!(matched(&filters, "a.b axb ids"), vec![(2, vec![(0, 3)])]);
+    assert!(matched(&filters, "nothing").is_empty());
+    assert!(!filters.is_match("nothing"));
+    assert_eq!(
+        filters.matches("😀 a.b")[0].color.as_deref(),
+        Some("#00ff00")
+    );
+    assert_eq!(matched(&filters, "😀 a.b"), vec![(2, vec![(3, 6)])]);
+}
+
+#[wasm_bindgen_test]
+fn empty_matches() {
+    let filters = match FilterSet::compile(String::from(
+        r#"[
+            {"filter": "x*", "flags": {"is_regex": true}},
+            {"filter": ""},
+            {"filter": "ab"}
+        ]"#,
+    )) {
+        Ok(filters) => filters,
+        Err(err) => panic!("{}", err),
+    };
+    assert_eq!(matched(&filters, "abc"), vec![(2, vec![(0, 2)])]);
+    assert_eq!(matched(&filters, "axx"), vec![(0, vec![(1, 3)])]);
+    assert!(matched(&filters, "nothing").is_empty());
+    assert!(!filters.is_match("nothing"));
+    assert!(filters.is_match("x"));
+    assert_eq!(filters.match_line("nothing"), "[]");
+}
+
+#[wasm_bindgen_test]
+fn json_output() {
+    let filters = filters();
+    assert_eq!(
+        filters.match_line("error"),
+        r##"[{"index":0,"color":"#ff0000","ranges":[{"start":0,"end":5}]}]"##
+    );
+    assert_eq!(
+        filters.match_lines(String::from(r#"["1ms", "none"]"#)),
+        Ok(String::from(
+            r#"[[{"index":1,"color":null,"ranges":[{"start":0,"end":3}]}],[]]"#
+        ))
+    );
+    assert!(filters.match_lines(String::from("[1]")).is_err());
+}
+
+#[wasm_bindgen_test]
+fn invalid_filters() {
+    let compile = |filters: &str| FilterSet::compile(filters.to_string()).err();
+    assert_eq!(
+        compile(r#"[{"filter": "ok"}, {"filter": "(", "flags": {"is_regex": true}}]"#)
+            .map(|err| err.starts_with("Filter #1 is invalid")),
+        Some(true)
+    );
+    assert!(compile(r#"[{"filter": "ok", "regex": true}]"#).is_some());
+    assert!(compile(r#"[{"filter": "(", "flags": {}}]"#).is_none());
+    assert!(FilterSet::compile(String::from("[]")).is_ok_and(|filters| filters.is_empty()));
+}
@@ -1 +1,4 @@
 pub mod filter;
+pub mod filter_set;
+pub mod lint;
+pub mod preview;
@@ -0,0 +1,281 @@
+use crate::filter::filter_as_regex;
+use regex::RegexBuilder;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+/// Size of compiled regex, above which filter is reported as suspicious. The
+/// default limit of regex crate (which produces an error) is 10Mb.
+const SUSPICIOUS_SIZE: usize = 1024 * 1024;
+/// Chars which make a string look like a regular expression (besides `.`)
+const REGEX_CHARS: &[char] = &[
+    '\\', '*', '+', '?', '(', ')', '[', ']', '{', '}', '|', '^', '$',
+];
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// Part of filter; offsets are in UTF-16 code units, so can be used with
+/// `String.prototype.slice`. End is exclusive.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Stable identifier of the kind of problem
+    pub code: &'static str,
+    pub message: String,
+    /// Problematic part of filter. None - the whole filter
+    pub span: Option<Span>,
+}
+
+impl Diagnostic {
+    fn warning(code: &'static str, message: &str, span: Option<Span>) -> Self {
+        Diagnostic {
+            severity: Severity::Warning,
+            code,
+            message: message.to_owned(),
+            span,
+        }
+    }
+}
+
+/// Checks filter and returns JSON array of diagnostics (see `Diagnostic`).
+/// Besides errors, reports warnings for valid, but suspicious filters.
+#[wasm_bindgen]
+pub fn lint_filter(filter: String, ignore_case: bool, whole_word: bool, regex: bool) -> String {
+    let diagnostics = lint(&filter, ignore_case, whole_word, regex);
+    serde_json::to_string(&diagnostics).unwrap_or_else(|_| String::from("[]"))
+}
+
+pub fn lint(filter: &str, ignore_case: bool, whole_word: bool, regex: bool) -> Vec<Diagnostic> {
+    if let Some(error) = regex.then(|| syntax_error(filter)).flatten() {
+        return vec![error];
+    }
+    let pattern = filter_as_regex(filter.to_owned(), ignore_case, whole_word, regex);
+    if let Err(err) = RegexBuilder::new(&pattern).build() {
+        let too_big = matches!(err, regex::Error::CompiledTooBig(_));
+        return vec![Diagnostic {
+            severity: Severity::Error,
+            code: if too_big { "too-big" } else { "invalid" },
+            message: if too_big {
+                String::from("Filter is too big to be compiled; reduce repetitions")
+            } else {
+                err.to_string()
+            },
+            span: None,
+        }];
+    }
+    let mut diagnostics = Vec::new();
+    if regex {
+        diagnostics.extend(wildcards(filter));
+        diagnostics.extend(dots(filter));
+    }
+    if whole_word {
+        diagnostics.extend(word_bounds(filter, regex));
+    }
+    if RegexBuilder::new(&pattern)
+        .size_limit(SUSPICIOUS_SIZE)
+        .build()
+        .is_err()
+    {
+        diagnostics.push(Diagnostic::warning(
+            "huge",
+            "Filter produces a huge regular expression, which may slow down search",
+            None,
+        ));
+    }
+    diagnostics
+}
+
+/// Converts byte range of text into span in UTF-16 code units
+pub(crate) fn span(filter: &str, start: usize, end: usize) -> Span {
+    let utf16 = |offset: usize| filter[..offset].encode_utf16().count();
+    Span {
+        start: utf16(start),
+        end: utf16(end),
+    }
+}
+
+/// Syntax error of filter as a regular expression with the position of error
+fn syntax_error(filter: &str) -> Option<Diagnostic> {
+    let (message, range) = match regex_syntax::Parser::new().parse(filter) {
+        Ok(_) => return None,
+        Err(regex_syntax::Error::Parse(err)) => (
+            err.kind().to_string(),
+            Some((err.span().start.offset, err.span().end.offset)),
+        ),
+        Err(regex_syntax::Error::Translate(err)) => (
+            err.kind().to_string(),
+            Some((err.span().start.offset, err.span().end.offset)),
+        ),
+        Err(err) => (err.to_string(), None),
+    };
+    Some(Diagnostic {
+        severity: Severity::Error,
+        code: "syntax",
+        message,
+        span: range.map(|(start, end)| span(filter, start, end)),
+    })
+}
+
+/// `.*` at the start or the end of filter: filters match a part of line anyway
+fn wildcards(filter: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    if filter.len() > 2 && filter.starts_with(".*") {
+        diagnostics.push(Diagnostic::warning(
+            "redundant-wildcard",
+            "Leading `.*` is redundant: filter matches any part of line",
+            Some(span(filter, 0, 2)),
+        ));
+    }
+    let end = filter.len();
+    if end > 2 && filter.ends_with(".*") && !escaped(filter, end - 2) {
+        diagnostics.push(Diagnostic::warning(
+            "redundant-wildcard",
+            "Trailing `.*` is redundant: filter matches any part of line",
+            Some(span(filter, end - 2, end)),
+        ));
+    }
+    diagnostics
+}
+
+/// Unescaped `.` in filter, which doesn't look like a regular expression
+/// (for example `file.txt` or `10.0.0.1`)
+fn dots(filter: &str) -> Vec<Diagnostic> {
+    if filter.contains(REGEX_CHARS) {
+        return Vec::new();
+    }
+    filter
+        .match_indices('.')
+        .map(|(pos, _)| {
+            Diagnostic::warning(
+                "unescaped-dot",
+                "`.` matches any character; use `\\.` to match a dot only",
+                Some(span(filter, pos, pos + 1)),
+            )
+        })
+        .collect()
+}
+
+/// Whole word flag with filter starting or ending with a non-word char. Word
+/// boundary next to such char requires a word char on the other side, so for
+/// example `[Warn]` doesn't match ` [Warn] `.
+fn word_bounds(filter: &str, regex: bool) -> Vec<Diagnostic> {
+    let literal = |pos: usize, ch: char| -> bool {
+        !regex || !REGEX_CHARS.contains(&ch) && ch != '.' || escaped(filter, pos)
+    };
+    let mut diagnostics = Vec::new();
+    let mut chars = filter.char_indices();
+    let first = if regex && filter.starts_with('\\') {
+        chars.nth(1).filter(|(_, ch)| !ch.is_alphanumeric())
+    } else {
+        chars.next().filter(|(pos, ch)| literal(*pos, *ch))
+    };
+    if let Some((pos, ch)) = first.filter(|(_, ch)| !is_word(*ch)) {
+        diagnostics.push(Diagnostic::warning(
+            "word-boundary",
+            "Filter starts with a non-word char; whole word search won't match it after a space",
+            Some(span(filter, 0, pos + ch.len_utf8())),
+        ));
+    }
+    let last = filter
+        .char_indices()
+        .last()
+        .filter(|(pos, ch)| *pos > 0 && literal(*pos, *ch));
+    if let Some((pos, ch)) = last.filter(|(_, ch)| !is_word(*ch)) {
+        let start = if regex && escaped(filter, pos) {
+            pos - 1
+        } else {
+            pos
+        };
+        diagnostics.push(Diagnostic::warning(
+            "word-boundary",
+            "Filter ends with a non-word char; whole word search won't match it before a space",
+            Some(span(filter, start, pos + ch.len_utf8())),
+        ));
+    }
+    diagnostics
+}
+
+fn is_word(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+/// true if char at byte position `pos` is escaped with odd number of backslashes
+fn escaped(filter: &str, pos: usize) -> bool {
+    filter[..pos]
+        .chars()
+        .rev()
+        .take_while(|ch| *ch == '\\')
+        .count()
+        % 2
+        == 1
+}
+
+fn codes(filter: &str, whole_word: bool, regex: bool) -> Vec<&'static str> {
+    lint(filter, false, whole_word, regex)
+        .iter()
+        .map(|diagnostic| diagnostic.code)
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn clean_filters() {
+    assert!(codes("Some random filter", false, true).is_empty());
+    assert!(codes(r"\[Warn\]", false, true).is_empty());
+    assert!(codes("file.txt", false, false).is_empty());
+    assert!(codes("error", true, true).is_empty());
+}
+
+#[wasm_bindgen_test]
+fn syntax_error_span() {
+    let diagnostics = lint(r"\[Warn(\]", false, false, true);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert_eq!(diagnostics[0].message, "unclosed group");
+    assert_eq!(diagnostics[0].span, Some(Span { start: 6, end: 7 }));
+    // Span is in UTF-16 code units
+    let diagnostics = lint("ü😀[z-a]", false, false, true);
+    assert_eq!(diagnostics[0].span, Some(Span { start: 4, end: 7 }));
+}
+
+#[wasm_bindgen_test]
+fn suspicious_filters() {
+    assert_eq!(
+        codes(".*error.*", false, true),
+        vec!["redundant-wildcard", "redundant-wildcard"]
+    );
+    assert!(codes(r"error\.*", false, true).is_empty());
+    assert_eq!(
+        codes("10.0.1", false, true),
+        vec!["unescaped-dot", "unescaped-dot"]
+    );
+    assert_eq!(codes(r"\w{50}", false, true), vec!["huge"]);
+    assert_eq!(
+        codes(r"\w{1000}\w{1000}\w{1000}", false, true),
+        vec!["too-big"]
+    );
+}
+
+#[wasm_bindgen_test]
+fn whole_word_with_non_word_chars() {
+    assert_eq!(
+        codes("[Warn]", true, false),
+        vec!["word-boundary", "word-boundary"]
+    );
+    let diagnostics = lint(r"\[Warn\]", false, true, true);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].span, Some(Span { start: 0, end: 2 }));
+    assert_eq!(diagnostics[1].span, Some(Span { start: 6, end: 8 }));
+    assert!(codes("(Warn|Error)", true, true).is_empty());
+    assert!(codes("Warn:", true, true).contains(&"word-boundary"));
+}
@@ -0,0 +1,149 @@
+use crate::{
+    filter::filter_as_regex,
+    lint::{span, Span},
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+/// Flags of filter; names are the same as in the filters of backend
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Flags {
+    pub is_regex: bool,
+    pub ignore_case: bool,
+    pub is_word: bool,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct LinePreview {
+    /// True if there is at least one range; empty matches (e.g. of `x*`)
+    /// aren't taken into account
+    pub matched: bool,
+    /// Not empty matches of filter in line
+    pub ranges: Vec<Span>,
+}
+
+/// Matches filter with sample lines without backend. `flags` is JSON object
+/// (see `Flags`), `lines_json` is JSON array of strings. Returns JSON array with
+/// `{"matched": bool, "ranges": [{"start": number, "end": number}]}` for each
+/// line; offsets are in UTF-16 code units.
+#[wasm_bindgen]
+pub fn preview_filter(filter: String, flags: String, lines_json: String) -> Result<String, String> {
+    let flags: Flags = serde_json::from_str(&flags)
+        .map_err(|err| format!("Parsing flags from JSON String failed: {}", err))?;
+    let lines: Vec<String> = serde_json::from_str(&lines_json)
+        .map_err(|err| format!("Parsing lines from JSON String failed: {}", err))?;
+    let previews = preview(filter, &flags, &lines)?;
+    serde_json::to_string(&previews).map_err(|err| err.to_string())
+}
+
+pub fn preview(
+    filter: String,
+    flags: &Flags,
+    lines: &[String],
+) -> Result<Vec<LinePreview>, String> {
+    let regex = Regex::from_str(&filter_as_regex(
+        filter,
+        flags.ignore_case,
+        flags.is_word,
+        flags.is_regex,
+    ))
+    .map_err(|err| err.to_string())?;
+    Ok(lines
+        .iter()
+        .map(|line| {
+            let ranges: Vec<Span> = regex
+                .find_iter(line)
+                .filter(|found| !found.is_empty())
+                .map(|found| span(line, found.start(), found.end()))
+                .collect();
+            LinePreview {
+                matched: !ranges.is_empty(),
+                ranges,
+            }
+        })
+        .collect())
+}
+
+fn ranges_of(filter: &str, flags: &str, lines: &str) -> Vec<Vec<(usize, usize)>> {
+    let previews: Vec<serde_json::Value> =
+        match preview_filter(filter.to_string(), flags.to_string(), lines.to_string()) {
+            Ok(previews) => serde_json::from_str(&previews).unwrap(),
+            Err(err) => panic!("{}", err),
+        };
+    previews
+        .iter()
+        .map(|preview| {
+            assert_eq!(
+                preview["matched"].as_bool(),
+                Some(!preview["ranges"].as_array().unwrap().is_empty())
+            );
+            preview["ranges"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|range| {
+                    (
+                        range["start"].as_u64().unwrap() as usize,
+                        range["end"].as_u64().unwrap() as usize,
+                    )
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn plain_filter() {
+    assert_eq!(
+        ranges_of("a.b", "{}", r#"["a.b a.b", "axb", ""]"#),
+        vec![vec![(0, 3), (4, 7)], vec![], vec![]]
+    );
+    assert_eq!(
+        ranges_of("A.B", r#"{"ignore_case": true}"#, r#"["xa.b"]"#),
+        vec![vec![(1, 4)]]
+    );
+    assert_eq!(ranges_of("A.B", "{}", r#"["a.b"]"#), vec![vec![]]);
+}
+
+#[wasm_bindgen_test]
+fn regex_filter() {
+    assert_eq!(
+        ranges_of(
+            r"\d+",
+            r#"{"is_regex": true}"#,
+            r#"["id 12 and 345", "none"]"#
+        ),
+        vec![vec![(3, 5), (10, 13)], vec![]]
+    );
+    assert_eq!(
+        ranges_of(
+            "err",
+            r#"{"is_regex": true, "is_word": true}"#,
+            r#"["err errors", "😀 err"]"#
+        ),
+        vec![vec![(0, 3)], vec![(3, 6)]]
+    );
+}
+
+#[wasm_bindgen_test]
+fn empty_matches() {
+    assert_eq!(
+        ranges_of("x*", r#"{"is_regex": true}"#, r#"["abc", "axxb", ""]"#),
+        vec![vec![], vec![(1, 3)], vec![]]
+    );
+}
+
+#[wasm_bindgen_test]
+fn invalid_input() {
+    let preview = |filter: &str, flags: &str, lines: &str| {
+        preview_filter(filter.to_string(), flags.to_string(), lines.to_string())
+    };
+    assert!(preview("(", r#"{"is_regex": true}"#, "[]").is_err());
+    assert!(preview("a", r#"{"regex": true}"#, "[]").is_err());
+    assert!(preview("a", "{}", r#"[1]"#).is_err());
+    assert_eq!(preview("a", "{}", "[]"), Ok(String::from("[]")));
+}
@@ -0,0 +1,78 @@
+use utils::{
+    filter_set::{FilterSet, SetFilter},
+    preview::Flags,
+};
+
+fn filter(filter: &str, flags: Flags, color: Option<&str>) -> SetFilter {
+    SetFilter {
+        filter: filter.to_string(),
+        flags,
+        color: color.map(String::from),
+    }
+}
+
+#[test]
+fn native_api() {
+    let filters = match FilterSet::new(vec![
+        filter(
+            "warn",
+            Flags {
+                ignore_case: true,
+                ..Flags::default()
+            },
+            Some("#ffff00"),
+        ),
+        filter(
+            r"code=\d+",
+            Flags {
+                is_regex: true,
+                ..Flags::default()
+            },
+            None,
+        ),
+    ]) {
+        Ok(filters) => filters,
+        Err(err) => panic!("{}", err),
+    };
+    let matches = filters.matches("WARN code=42 warn");
+    assert_eq!(
+        matches
+            .iter()
+            .map(|found| (found.index, found.ranges.len()))
+            .collect::<Vec<_>>(),
+        vec![(0, 2), (1, 1)]
+    );
+    assert_eq!(matches[0].color.as_deref(), Some("#ffff00"));
+    assert_eq!(
+        (matches[1].ranges[0].start, matches[1].ranges[0].end),
+        (5, 12)
+    );
+    assert!(!filters.is_match("info"));
+    // Filters matching empty strings only are ignored
+    let filters = match FilterSet::new(vec![
+        filter(
+            "x*",
+            Flags {
+                is_regex: true,
+                ..Flags::default()
+            },
+            None,
+        ),
+        filter("", Flags::default(), None),
+    ]) {
+        Ok(filters) => filters,
+        Err(err) => panic!("{}", err),
+    };
+    assert!(filters.matches("info").is_empty());
+    assert!(!filters.is_match("info"));
+    assert_eq!(filters.matches("xx").len(), 1);
+    assert!(FilterSet::new(vec![filter(
+        "(",
+        Flags {
+            is_regex: true,
+            ..Flags::default()
+        },
+        None
+    )])
+    .is_err());
+}
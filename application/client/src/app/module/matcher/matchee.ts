@@ -63,7 +63,7 @@ export function createPassiveMatcheeList<T extends PassiveMatchee>(
     list: T[],
     matcher: wasm.Matcher,
 ): T[] {
-    const from: number = matcher.set_items(JSON.stringify(list.map((i) => i.asObj())));
+    const from: number = matcher.set_items(list.map((i) => i.asObj()));
     list.forEach((item, i) => {
         item.setIndex(from + i);
     });
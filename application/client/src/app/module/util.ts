@@ -13,3 +13,47 @@ export function getFilterError(
         return undefined;
     }
 }
+
+export interface FilterDiagnostic {
+    severity: 'error' | 'warning';
+    code: string;
+    message: string;
+    // Offsets in the filter; whole filter if not defined
+    span: { start: number; end: number } | null;
+}
+
+export function lintFilter(
+    filter: string,
+    ignoreCase: boolean,
+    wholeWord: boolean,
+    regex: boolean,
+): FilterDiagnostic[] {
+    try {
+        return JSON.parse(
+            wasm.getUtils().lint_filter(filter, ignoreCase, wholeWord, regex),
+        ) as FilterDiagnostic[];
+    } catch (e) {
+        return [];
+    }
+}
+
+export interface FilterPreview {
+    matched: boolean;
+    ranges: { start: number; end: number }[];
+}
+
+export function previewFilter(
+    filter: string,
+    flags: { is_regex: boolean; ignore_case: boolean; is_word: boolean },
+    lines: string[],
+): FilterPreview[] | Error {
+    try {
+        return JSON.parse(
+            wasm
+                .getUtils()
+                .preview_filter(filter, JSON.stringify(flags), JSON.stringify(lines)),
+        ) as FilterPreview[];
+    } catch (e) {
+        return new Error(`${e}`);
+    }
+}
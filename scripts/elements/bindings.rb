@@ -35,12 +35,13 @@ class Bindings
     end
   end
 
-  def build
+  def build(features = [])
     Environment.check
     install
     Platform.check(Paths::TS_BINDINGS, false)
     Shell.chdir(Paths::RS_BINDINGS) do
-      Shell.sh "./#{@build_env} #{@nj_cli} build --release"
+      extras = features.empty? ? '' : " -- --features #{features.join(',')}"
+      Shell.sh "./#{@build_env} #{@nj_cli} build --release#{extras}"
       Reporter.add(Jobs::Building, Owner::Bindings, 'rs bindings', '')
     end
     Shell.chdir(Paths::TS_BINDINGS) do